@@ -0,0 +1,120 @@
+use std::collections::BTreeMap;
+use std::io::Cursor;
+use std::ops::Range;
+
+use crate::tables::font_header::FontHeader;
+use crate::tables::offsets::{Header, Offsets, Record};
+use crate::{Result, Tag, ALIGNMENT};
+
+/// A builder of fonts.
+///
+/// The tables are sorted by tag, aligned to 4-byte boundaries, and
+/// checksummed, and the checksum adjustment of the font header is updated
+/// accordingly.
+#[derive(Clone, Debug)]
+pub struct FontBuilder {
+    version: Tag,
+    tables: BTreeMap<Tag, Vec<u8>>,
+}
+
+impl FontBuilder {
+    /// Create an instance given a version, such as `0x00010000` for TrueType
+    /// outlines or `OTTO` for CFF outlines.
+    pub fn new(version: Tag) -> Result<Self> {
+        if !Header::accept(&version) {
+            raise!("found an unknown font format");
+        }
+        Ok(Self {
+            version,
+            tables: Default::default(),
+        })
+    }
+
+    /// Add a table given its data, replacing any table with the same tag.
+    pub fn add(&mut self, tag: Tag, data: Vec<u8>) -> &mut Self {
+        self.tables.insert(tag, data);
+        self
+    }
+
+    /// Add a table given its value, replacing any table with the same tag.
+    pub fn add_table<T: crate::value::Write>(&mut self, tag: Tag, value: &T) -> Result<&mut Self> {
+        let mut data = vec![];
+        crate::tape::Write::give(&mut data, value)?;
+        Ok(self.add(tag, data))
+    }
+
+    /// Assemble the font.
+    pub fn build(&self) -> Result<Vec<u8>> {
+        use crate::tape::Write;
+
+        let table_count = self.tables.len();
+        if table_count > u16::MAX as usize {
+            raise!("found too many tables");
+        }
+        let entry_selector = table_count.max(1).ilog2() as usize;
+        let search_range = 16 * (1 << entry_selector);
+        let header = Header {
+            version: self.version.into(),
+            table_count: table_count as _,
+            search_range: search_range as _,
+            entry_selector: entry_selector as _,
+            range_shift: (16 * table_count).saturating_sub(search_range) as _,
+        };
+
+        let mut offset = 12 + 16 * table_count;
+        let mut records = Vec::with_capacity(table_count);
+        for (tag, data) in self.tables.iter() {
+            if tag == b"head" && data.len() < FontHeader::CHECKSUM_ADJUSTMENT_RANGE.end {
+                raise!("found a malformed font header");
+            }
+            if offset > u32::MAX as usize || data.len() > u32::MAX as usize {
+                raise!("found too much data to address");
+            }
+            records.push(Record {
+                tag: *tag,
+                checksum: 0,
+                offset: offset as _,
+                size: data.len() as _,
+            });
+            offset += data.len().next_multiple_of(ALIGNMENT);
+        }
+
+        let mut data = Vec::with_capacity(offset);
+        data.resize(12 + 16 * table_count, 0);
+        for (record, table) in records.iter().zip(self.tables.values()) {
+            debug_assert_eq!(data.len(), record.offset as usize);
+            data.give_bytes(table)?;
+            if record.tag == b"head" {
+                data[adjustment(record)].fill(0);
+            }
+            data.resize(data.len().next_multiple_of(ALIGNMENT), 0);
+        }
+        {
+            let mut tape = Cursor::new(&data);
+            for record in records.iter_mut() {
+                record.checksum = record.checksum(&mut tape)?;
+            }
+        }
+        let adjustment = records
+            .iter()
+            .find(|record| record.tag == b"head")
+            .map(adjustment);
+        let offsets = Offsets { header, records };
+        Cursor::new(&mut data).give(&offsets)?;
+        if let Some(range) = adjustment {
+            let value = FontHeader::checksum(&mut Cursor::new(&data))?;
+            data[range].copy_from_slice(&value.to_be_bytes());
+        }
+        Ok(data)
+    }
+
+    /// Assemble and write the font.
+    pub fn write<T: crate::tape::Write>(&self, tape: &mut T) -> Result<()> {
+        tape.give_bytes(&self.build()?)
+    }
+}
+
+fn adjustment(record: &Record) -> Range<usize> {
+    let range = FontHeader::CHECKSUM_ADJUSTMENT_RANGE;
+    (record.offset as usize + range.start)..(record.offset as usize + range.end)
+}
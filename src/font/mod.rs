@@ -0,0 +1,256 @@
+//! A font.
+
+mod builder;
+
+pub use builder::FontBuilder;
+
+use crate::tables::offsets::{Offsets, Record};
+use crate::tables::{
+    CharacterMapping, ControlValueProgram, ControlValues, FontHeader, FontProgram, GlyphData,
+    GlyphMapping, HorizontalDeviceMetrics, HorizontalHeader, HorizontalMetrics, MaximumProfile,
+    Names, PostScript, VerticalHeader, VerticalMetrics, WindowsMetrics,
+};
+use crate::{Result, Tag};
+
+/// A font.
+///
+/// The offset table is read upfront, and the tables are read on demand and
+/// cached.
+pub struct Font<T> {
+    /// The offset table.
+    pub offsets: Offsets,
+
+    tape: T,
+    cache: Cache,
+}
+
+#[derive(Default)]
+struct Cache {
+    character_mapping: Option<CharacterMapping>,
+    control_value_program: Option<ControlValueProgram>,
+    control_values: Option<ControlValues>,
+    font_header: Option<FontHeader>,
+    font_program: Option<FontProgram>,
+    glyph_data: Option<GlyphData>,
+    glyph_mapping: Option<GlyphMapping>,
+    horizontal_device_metrics: Option<HorizontalDeviceMetrics>,
+    horizontal_header: Option<HorizontalHeader>,
+    horizontal_metrics: Option<HorizontalMetrics>,
+    maximum_profile: Option<MaximumProfile>,
+    names: Option<Names>,
+    postscript: Option<PostScript>,
+    vertical_header: Option<VerticalHeader>,
+    vertical_metrics: Option<VerticalMetrics>,
+    windows_metrics: Option<WindowsMetrics>,
+}
+
+macro_rules! implement {
+    ($(#[$attribute:meta])* $field:ident, $type:ident, $tag:expr) => (
+        $(#[$attribute])*
+        pub fn $field(&mut self) -> Result<&$type> {
+            if self.cache.$field.is_none() {
+                self.cache.$field = Some(self.take(Tag(*$tag))?);
+            }
+            Ok(self.cache.$field.as_ref().unwrap())
+        }
+    );
+    (@size $(#[$attribute:meta])* $field:ident, $type:ident, $tag:expr) => (
+        $(#[$attribute])*
+        pub fn $field(&mut self) -> Result<&$type> {
+            if self.cache.$field.is_none() {
+                let size = self.jump(Tag(*$tag))?.size as usize;
+                self.cache.$field = Some(self.tape.take_given(size)?);
+            }
+            Ok(self.cache.$field.as_ref().unwrap())
+        }
+    );
+}
+
+impl<T: crate::tape::Read> Font<T> {
+    /// Read the offset table and create an instance.
+    pub fn read(mut tape: T) -> Result<Self> {
+        let offsets = crate::tape::Read::take(&mut tape)?;
+        Ok(Self {
+            offsets,
+            tape,
+            cache: Default::default(),
+        })
+    }
+
+    /// Find the record of a table.
+    pub fn find(&self, tag: Tag) -> Option<&Record> {
+        self.offsets.records.iter().find(|record| record.tag == tag)
+    }
+
+    /// Check if a table is present.
+    #[inline]
+    pub fn contains(&self, tag: Tag) -> bool {
+        self.find(tag).is_some()
+    }
+
+    /// Read a table without caching it.
+    pub fn take<U: crate::value::Read>(&mut self, tag: Tag) -> Result<U> {
+        self.jump(tag)?;
+        crate::tape::Read::take(&mut self.tape)
+    }
+
+    /// Read a table given a parameter without caching it.
+    pub fn take_given<'l, U: crate::walue::Read<'l>>(
+        &mut self,
+        tag: Tag,
+        parameter: U::Parameter,
+    ) -> Result<U> {
+        self.jump(tag)?;
+        self.tape.take_given(parameter)
+    }
+
+    /// Read the raw data of a table.
+    pub fn take_bytes(&mut self, tag: Tag) -> Result<Vec<u8>> {
+        let size = self.jump(tag)?.size;
+        self.tape.take_bytes(size as usize)
+    }
+
+    implement! {
+        /// Return the character-to-glyph mapping.
+        character_mapping, CharacterMapping, b"cmap"
+    }
+
+    implement! {
+        @size
+        /// Return the control-value program.
+        control_value_program, ControlValueProgram, b"prep"
+    }
+
+    implement! {
+        @size
+        /// Return the control values.
+        control_values, ControlValues, b"cvt "
+    }
+
+    implement! {
+        /// Return the font header.
+        font_header, FontHeader, b"head"
+    }
+
+    implement! {
+        @size
+        /// Return the font program.
+        font_program, FontProgram, b"fpgm"
+    }
+
+    implement! {
+        /// Return the horizontal header.
+        horizontal_header, HorizontalHeader, b"hhea"
+    }
+
+    implement! {
+        /// Return the maximum profile.
+        maximum_profile, MaximumProfile, b"maxp"
+    }
+
+    implement! {
+        /// Return the naming table.
+        names, Names, b"name"
+    }
+
+    implement! {
+        /// Return the PostScript table.
+        postscript, PostScript, b"post"
+    }
+
+    implement! {
+        /// Return the vertical header.
+        vertical_header, VerticalHeader, b"vhea"
+    }
+
+    implement! {
+        /// Return the OS/2 and Windows metrics.
+        windows_metrics, WindowsMetrics, b"OS/2"
+    }
+
+    /// Return the glyph data.
+    pub fn glyph_data(&mut self) -> Result<&GlyphData> {
+        if self.cache.glyph_data.is_none() {
+            self.glyph_mapping()?;
+            self.jump(Tag(*b"glyf"))?;
+            let value = self
+                .tape
+                .take_given(self.cache.glyph_mapping.as_ref().unwrap())?;
+            self.cache.glyph_data = Some(value);
+        }
+        Ok(self.cache.glyph_data.as_ref().unwrap())
+    }
+
+    /// Return the glyph-to-location mapping.
+    pub fn glyph_mapping(&mut self) -> Result<&GlyphMapping> {
+        if self.cache.glyph_mapping.is_none() {
+            self.font_header()?;
+            self.maximum_profile()?;
+            self.jump(Tag(*b"loca"))?;
+            let value = self.tape.take_given((
+                self.cache.font_header.as_ref().unwrap(),
+                self.cache.maximum_profile.as_ref().unwrap(),
+            ))?;
+            self.cache.glyph_mapping = Some(value);
+        }
+        Ok(self.cache.glyph_mapping.as_ref().unwrap())
+    }
+
+    /// Return the horizontal device metrics.
+    pub fn horizontal_device_metrics(&mut self) -> Result<&HorizontalDeviceMetrics> {
+        if self.cache.horizontal_device_metrics.is_none() {
+            self.maximum_profile()?;
+            self.jump(Tag(*b"hdmx"))?;
+            let value = self
+                .tape
+                .take_given(self.cache.maximum_profile.as_ref().unwrap())?;
+            self.cache.horizontal_device_metrics = Some(value);
+        }
+        Ok(self.cache.horizontal_device_metrics.as_ref().unwrap())
+    }
+
+    /// Return the horizontal metrics.
+    pub fn horizontal_metrics(&mut self) -> Result<&HorizontalMetrics> {
+        if self.cache.horizontal_metrics.is_none() {
+            self.horizontal_header()?;
+            self.maximum_profile()?;
+            self.jump(Tag(*b"hmtx"))?;
+            let value = self.tape.take_given((
+                self.cache.horizontal_header.as_ref().unwrap(),
+                self.cache.maximum_profile.as_ref().unwrap(),
+            ))?;
+            self.cache.horizontal_metrics = Some(value);
+        }
+        Ok(self.cache.horizontal_metrics.as_ref().unwrap())
+    }
+
+    /// Return the vertical metrics.
+    pub fn vertical_metrics(&mut self) -> Result<&VerticalMetrics> {
+        if self.cache.vertical_metrics.is_none() {
+            self.vertical_header()?;
+            self.maximum_profile()?;
+            self.jump(Tag(*b"vmtx"))?;
+            let value = self.tape.take_given((
+                self.cache.vertical_header.as_ref().unwrap(),
+                self.cache.maximum_profile.as_ref().unwrap(),
+            ))?;
+            self.cache.vertical_metrics = Some(value);
+        }
+        Ok(self.cache.vertical_metrics.as_ref().unwrap())
+    }
+
+    /// Return the underlying tape.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.tape
+    }
+
+    fn jump(&mut self, tag: Tag) -> Result<Record> {
+        let record = match self.find(tag) {
+            Some(record) => *record,
+            _ => raise!("found no table with tag {tag:?}"),
+        };
+        self.tape.jump(record.offset as u64)?;
+        Ok(record)
+    }
+}
@@ -0,0 +1,556 @@
+//! The [instruction set][1].
+//!
+//! Instructions are rendered in the conventional text form, one per line, with
+//! the flags of an instruction given in binary within brackets followed by the
+//! operands of push instructions, as in `MIRP[01101]` and `PUSHB[001] 7 42`.
+//! A push instruction with a number of operands that cannot be encoded is
+//! rendered with empty brackets, which are rejected when parsed back.
+//!
+//! [1]: https://learn.microsoft.com/en-us/typography/opentype/spec/tt_instructions
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Result;
+
+macro_rules! implement {
+    (
+        plain {
+            $($plain_opcode:literal => $plain:ident, $plain_doc:literal,)*
+        }
+        flagged {
+            $($flagged_opcode:literal, $width:literal => $flagged:ident, $flagged_doc:literal,)*
+        }
+    ) => (
+        /// An instruction.
+        #[allow(clippy::upper_case_acronyms)]
+        #[derive(Clone, Debug, Eq, PartialEq)]
+        pub enum Instruction {
+            $(#[doc = $plain_doc] $plain,)*
+            $(#[doc = $flagged_doc] $flagged(u8),)*
+            /// Push a counted number of bytes.
+            NPUSHB(Vec<u8>),
+            /// Push a counted number of words.
+            NPUSHW(Vec<i16>),
+            /// Push from one to eight bytes.
+            PUSHB(Vec<u8>),
+            /// Push from one to eight words.
+            PUSHW(Vec<i16>),
+            /// An unknown instruction.
+            Unknown(u8),
+        }
+
+        impl Instruction {
+            /// Return the name.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Instruction::$plain => stringify!($plain),)*
+                    $(Instruction::$flagged(_) => stringify!($flagged),)*
+                    Instruction::NPUSHB(_) => "NPUSHB",
+                    Instruction::NPUSHW(_) => "NPUSHW",
+                    Instruction::PUSHB(_) => "PUSHB",
+                    Instruction::PUSHW(_) => "PUSHW",
+                    Instruction::Unknown(_) => "UNKNOWN",
+                }
+            }
+
+            fn from_opcode(opcode: u8) -> Self {
+                match opcode {
+                    $($plain_opcode => return Instruction::$plain,)*
+                    _ => {}
+                }
+                $(
+                    if opcode.wrapping_sub($flagged_opcode) < 1 << $width {
+                        return Instruction::$flagged(opcode - $flagged_opcode);
+                    }
+                )*
+                Instruction::Unknown(opcode)
+            }
+
+            fn from_name(name: &str, flags: &str) -> Result<Self> {
+                macro_rules! reject(
+                    () => (raise!("found a malformed instruction {name}[{flags}]"));
+                );
+                match name {
+                    $(stringify!($plain) => {
+                        if !flags.is_empty() {
+                            reject!();
+                        }
+                        return Ok(Instruction::$plain);
+                    })*
+                    $(stringify!($flagged) => {
+                        if flags.len() != $width {
+                            reject!();
+                        }
+                        return match u8::from_str_radix(flags, 2) {
+                            Ok(value) => Ok(Instruction::$flagged(value)),
+                            _ => reject!(),
+                        };
+                    })*
+                    _ => {}
+                }
+                raise!("found an unknown instruction {name}")
+            }
+
+            fn flags(&self) -> Option<(u8, usize)> {
+                match self {
+                    $(Instruction::$flagged(value) => Some((*value, $width)),)*
+                    Instruction::PUSHB(_) | Instruction::PUSHW(_) => {
+                        Some((check(self).ok()? as u8 - 1, 3))
+                    }
+                    _ => None,
+                }
+            }
+
+            fn opcode(&self) -> Result<u8> {
+                let count = check(self)?;
+                Ok(match self {
+                    $(Instruction::$plain => $plain_opcode,)*
+                    $(Instruction::$flagged(value) => {
+                        if *value >= 1 << $width {
+                            raise!("found a malformed instruction {self}");
+                        }
+                        $flagged_opcode + value
+                    })*
+                    Instruction::NPUSHB(_) => 0x40,
+                    Instruction::NPUSHW(_) => 0x41,
+                    Instruction::PUSHB(_) => 0xB0 + count as u8 - 1,
+                    Instruction::PUSHW(_) => 0xB8 + count as u8 - 1,
+                    Instruction::Unknown(opcode) => *opcode,
+                })
+            }
+        }
+    );
+}
+
+implement! {
+    plain {
+        0x0A => SPVFS, "Set the projection vector from the stack.",
+        0x0B => SFVFS, "Set the freedom vector from the stack.",
+        0x0C => GPV, "Get the projection vector.",
+        0x0D => GFV, "Get the freedom vector.",
+        0x0E => SFVTPV, "Set the freedom vector to the projection vector.",
+        0x0F => ISECT, "Move a point to the intersection of two lines.",
+        0x10 => SRP0, "Set reference point 0.",
+        0x11 => SRP1, "Set reference point 1.",
+        0x12 => SRP2, "Set reference point 2.",
+        0x13 => SZP0, "Set zone pointer 0.",
+        0x14 => SZP1, "Set zone pointer 1.",
+        0x15 => SZP2, "Set zone pointer 2.",
+        0x16 => SZPS, "Set all zone pointers.",
+        0x17 => SLOOP, "Set the loop variable.",
+        0x18 => RTG, "Round to the grid.",
+        0x19 => RTHG, "Round to the half grid.",
+        0x1A => SMD, "Set the minimum distance.",
+        0x1B => ELSE, "Start the alternative of a conditional block.",
+        0x1C => JMPR, "Jump relative.",
+        0x1D => SCVTCI, "Set the control-value cut-in.",
+        0x1E => SSWCI, "Set the single-width cut-in.",
+        0x1F => SSW, "Set the single width.",
+        0x20 => DUP, "Duplicate the top of the stack.",
+        0x21 => POP, "Pop the top of the stack.",
+        0x22 => CLEAR, "Clear the stack.",
+        0x23 => SWAP, "Swap the top two elements of the stack.",
+        0x24 => DEPTH, "Push the depth of the stack.",
+        0x25 => CINDEX, "Copy an indexed element to the top of the stack.",
+        0x26 => MINDEX, "Move an indexed element to the top of the stack.",
+        0x27 => ALIGNPTS, "Align two points.",
+        0x29 => UTP, "Untouch a point.",
+        0x2A => LOOPCALL, "Call a function repeatedly.",
+        0x2B => CALL, "Call a function.",
+        0x2C => FDEF, "Define a function.",
+        0x2D => ENDF, "End a function or instruction definition.",
+        0x38 => SHPIX, "Shift points by a distance in pixels.",
+        0x39 => IP, "Interpolate points.",
+        0x3C => ALIGNRP, "Align points to the reference point.",
+        0x3D => RTDG, "Round to the double grid.",
+        0x42 => WS, "Write to the storage area.",
+        0x43 => RS, "Read from the storage area.",
+        0x44 => WCVTP, "Write a control value in pixels.",
+        0x45 => RCVT, "Read a control value.",
+        0x48 => SCFS, "Set a coordinate from the stack.",
+        0x4B => MPPEM, "Measure pixels per em.",
+        0x4C => MPS, "Measure the point size.",
+        0x4D => FLIPON, "Turn the auto-flip on.",
+        0x4E => FLIPOFF, "Turn the auto-flip off.",
+        0x4F => DEBUG, "Debug.",
+        0x50 => LT, "Less than.",
+        0x51 => LTEQ, "Less than or equal.",
+        0x52 => GT, "Greater than.",
+        0x53 => GTEQ, "Greater than or equal.",
+        0x54 => EQ, "Equal.",
+        0x55 => NEQ, "Not equal.",
+        0x56 => ODD, "Odd.",
+        0x57 => EVEN, "Even.",
+        0x58 => IF, "Start a conditional block.",
+        0x59 => EIF, "End a conditional block.",
+        0x5A => AND, "Logical and.",
+        0x5B => OR, "Logical or.",
+        0x5C => NOT, "Logical not.",
+        0x5D => DELTAP1, "Apply exceptions to points in the first range.",
+        0x5E => SDB, "Set the delta base.",
+        0x5F => SDS, "Set the delta shift.",
+        0x60 => ADD, "Add.",
+        0x61 => SUB, "Subtract.",
+        0x62 => DIV, "Divide.",
+        0x63 => MUL, "Multiply.",
+        0x64 => ABS, "Absolute value.",
+        0x65 => NEG, "Negate.",
+        0x66 => FLOOR, "Floor.",
+        0x67 => CEILING, "Ceiling.",
+        0x70 => WCVTF, "Write a control value in font units.",
+        0x71 => DELTAP2, "Apply exceptions to points in the second range.",
+        0x72 => DELTAP3, "Apply exceptions to points in the third range.",
+        0x73 => DELTAC1, "Apply exceptions to control values in the first range.",
+        0x74 => DELTAC2, "Apply exceptions to control values in the second range.",
+        0x75 => DELTAC3, "Apply exceptions to control values in the third range.",
+        0x76 => SROUND, "Set the rounding state precisely.",
+        0x77 => S45ROUND, "Set the rounding state precisely for diagonals.",
+        0x78 => JROT, "Jump relative on true.",
+        0x79 => JROF, "Jump relative on false.",
+        0x7A => ROFF, "Turn the rounding off.",
+        0x7C => RUTG, "Round up to the grid.",
+        0x7D => RDTG, "Round down to the grid.",
+        0x7E => SANGW, "Set the angle weight.",
+        0x7F => AA, "Adjust the angle.",
+        0x80 => FLIPPT, "Flip points.",
+        0x81 => FLIPRGON, "Flip a range of points on.",
+        0x82 => FLIPRGOFF, "Flip a range of points off.",
+        0x85 => SCANCTRL, "Control the scan conversion.",
+        0x88 => GETINFO, "Get information.",
+        0x89 => IDEF, "Define an instruction.",
+        0x8A => ROLL, "Roll the top three elements of the stack.",
+        0x8B => MAX, "Maximum.",
+        0x8C => MIN, "Minimum.",
+        0x8D => SCANTYPE, "Set the scan type.",
+        0x8E => INSTCTRL, "Control the instruction execution.",
+        0x91 => GETVARIATION, "Get the variation coordinates.",
+        0x92 => GETDATA, "Get data.",
+    }
+    flagged {
+        0x00, 1 => SVTCA, "Set the freedom and projection vectors to an axis.",
+        0x02, 1 => SPVTCA, "Set the projection vector to an axis.",
+        0x04, 1 => SFVTCA, "Set the freedom vector to an axis.",
+        0x06, 1 => SPVTL, "Set the projection vector to a line.",
+        0x08, 1 => SFVTL, "Set the freedom vector to a line.",
+        0x2E, 1 => MDAP, "Move a point directly to an absolute position.",
+        0x30, 1 => IUP, "Interpolate untouched points.",
+        0x32, 1 => SHP, "Shift points by the reference point.",
+        0x34, 1 => SHC, "Shift a contour by the reference point.",
+        0x36, 1 => SHZ, "Shift a zone by the reference point.",
+        0x3A, 1 => MSIRP, "Move a point to a stack-indirect relative position.",
+        0x3E, 1 => MIAP, "Move a point to an indirect absolute position.",
+        0x46, 1 => GC, "Get a coordinate.",
+        0x49, 1 => MD, "Measure a distance.",
+        0x68, 2 => ROUND, "Round a value.",
+        0x6C, 2 => NROUND, "Compensate a value without rounding.",
+        0x86, 1 => SDPVTL, "Set the dual projection vector to a line.",
+        0xC0, 5 => MDRP, "Move a point directly to a relative position.",
+        0xE0, 5 => MIRP, "Move a point to an indirect relative position.",
+    }
+}
+
+/// Decode instructions.
+///
+/// The conditional blocks are required to be properly nested.
+pub fn decode(data: &[u8]) -> Result<Vec<Instruction>> {
+    macro_rules! take(
+        ($offset:ident, $count:expr) => ({
+            let start = $offset;
+            $offset += $count;
+            match data.get(start..$offset) {
+                Some(value) => value,
+                _ => raise!("found a truncated instruction at offset {}", start - 1),
+            }
+        });
+    );
+    fn words(data: &[u8]) -> Vec<i16> {
+        data.chunks(2)
+            .map(|chunk| i16::from_be_bytes([chunk[0], chunk[1]]))
+            .collect()
+    }
+    let mut instructions = vec![];
+    let mut depth = 0usize;
+    let mut offset = 0;
+    while offset < data.len() {
+        let opcode = data[offset];
+        offset += 1;
+        let instruction = match opcode {
+            0x40 => {
+                let count = take!(offset, 1)[0] as usize;
+                Instruction::NPUSHB(take!(offset, count).to_vec())
+            }
+            0x41 => {
+                let count = take!(offset, 1)[0] as usize;
+                Instruction::NPUSHW(words(take!(offset, 2 * count)))
+            }
+            0xB0..=0xB7 => Instruction::PUSHB(take!(offset, (opcode - 0xB0 + 1) as usize).to_vec()),
+            0xB8..=0xBF => {
+                Instruction::PUSHW(words(take!(offset, 2 * (opcode - 0xB8 + 1) as usize)))
+            }
+            _ => Instruction::from_opcode(opcode),
+        };
+        match instruction {
+            Instruction::IF => depth += 1,
+            Instruction::ELSE if depth == 0 => {
+                raise!("found an unmatched ELSE at offset {}", offset - 1)
+            }
+            Instruction::EIF if depth == 0 => {
+                raise!("found an unmatched EIF at offset {}", offset - 1)
+            }
+            Instruction::EIF => depth -= 1,
+            _ => {}
+        }
+        instructions.push(instruction);
+    }
+    if depth > 0 {
+        raise!("found an unterminated IF");
+    }
+    Ok(instructions)
+}
+
+/// Encode instructions.
+pub fn encode(instructions: &[Instruction]) -> Result<Vec<u8>> {
+    let mut data = vec![];
+    for instruction in instructions {
+        match instruction {
+            Instruction::NPUSHB(values) | Instruction::PUSHB(values) => {
+                data.push(instruction.opcode()?);
+                if let Instruction::NPUSHB(_) = instruction {
+                    data.push(values.len() as u8);
+                }
+                data.extend_from_slice(values);
+            }
+            Instruction::NPUSHW(values) | Instruction::PUSHW(values) => {
+                data.push(instruction.opcode()?);
+                if let Instruction::NPUSHW(_) = instruction {
+                    data.push(values.len() as u8);
+                }
+                for value in values {
+                    data.extend_from_slice(&value.to_be_bytes());
+                }
+            }
+            _ => data.push(instruction.opcode()?),
+        }
+    }
+    Ok(data)
+}
+
+/// Decode instructions and render them as text.
+///
+/// The instructions within conditional blocks and definitions are indented.
+pub fn disassemble(data: &[u8]) -> Result<String> {
+    let mut text = String::new();
+    let mut depth = 0usize;
+    for instruction in decode(data)? {
+        let indent = match instruction {
+            Instruction::ELSE => depth.saturating_sub(1),
+            Instruction::EIF | Instruction::ENDF => {
+                depth = depth.saturating_sub(1);
+                depth
+            }
+            _ => depth,
+        };
+        if let Instruction::IF | Instruction::FDEF | Instruction::IDEF = instruction {
+            depth += 1;
+        }
+        for _ in 0..indent {
+            text.push_str("  ");
+        }
+        text.push_str(&instruction.to_string());
+        text.push('\n');
+    }
+    Ok(text)
+}
+
+/// Parse instructions from text and encode them.
+///
+/// Each line contains at most one instruction, and anything after `//` is
+/// ignored.
+pub fn assemble(text: &str) -> Result<Vec<u8>> {
+    let instructions = text
+        .lines()
+        .map(|line| line.split("//").next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .map(str::parse)
+        .collect::<Result<Vec<Instruction>>>()?;
+    encode(&instructions)
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.name())?;
+        match self.flags() {
+            Some((value, width)) => write!(formatter, "[{value:0width$b}]")?,
+            _ => match self {
+                Instruction::Unknown(opcode) => write!(formatter, "[{opcode:#04X}]")?,
+                _ => formatter.write_str("[]")?,
+            },
+        }
+        match self {
+            Instruction::NPUSHB(values) | Instruction::PUSHB(values) => {
+                for value in values {
+                    write!(formatter, " {value}")?;
+                }
+            }
+            Instruction::NPUSHW(values) | Instruction::PUSHW(values) => {
+                for value in values {
+                    write!(formatter, " {value}")?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = crate::Error;
+
+    fn from_str(line: &str) -> Result<Self> {
+        let mut tokens = line.split_whitespace();
+        let token = tokens.next().unwrap_or_default();
+        let (name, flags) = match token.split_once('[') {
+            Some((name, flags)) => match flags.strip_suffix(']') {
+                Some(flags) => (name, flags),
+                _ => raise!("found a malformed instruction {token}"),
+            },
+            _ => (token, ""),
+        };
+        let operands = tokens.collect::<Vec<_>>();
+        macro_rules! parse(
+            ($type:ty) => (
+                operands
+                    .iter()
+                    .map(|operand| match operand.parse::<$type>() {
+                        Ok(value) => Ok(value),
+                        _ => raise!("found a malformed operand {operand} of {name}"),
+                    })
+                    .collect::<Result<Vec<_>>>()?
+            );
+        );
+        let instruction = match name {
+            "NPUSHB" | "NPUSHW" if !flags.is_empty() => {
+                raise!("found a malformed instruction {token}")
+            }
+            "NPUSHB" => Instruction::NPUSHB(parse!(u8)),
+            "NPUSHW" => Instruction::NPUSHW(parse!(i16)),
+            "PUSHB" | "PUSHW" => {
+                let count = match u8::from_str_radix(flags, 2) {
+                    Ok(value) if flags.len() == 3 => value as usize + 1,
+                    _ => raise!("found a malformed instruction {token}"),
+                };
+                if operands.len() != count {
+                    raise!("found a wrong number of operands of {token}");
+                }
+                if name == "PUSHB" {
+                    Instruction::PUSHB(parse!(u8))
+                } else {
+                    Instruction::PUSHW(parse!(i16))
+                }
+            }
+            "UNKNOWN" => match flags
+                .strip_prefix("0x")
+                .map(|value| u8::from_str_radix(value, 16))
+            {
+                Some(Ok(opcode)) => Instruction::Unknown(opcode),
+                _ => raise!("found a malformed instruction {token}"),
+            },
+            _ => {
+                if !operands.is_empty() {
+                    raise!("found unexpected operands of {token}");
+                }
+                Instruction::from_name(name, flags)?
+            }
+        };
+        Ok(instruction)
+    }
+}
+
+// Check the number of operands of a push instruction and return it. Other
+// instructions have no operands.
+fn check(instruction: &Instruction) -> Result<usize> {
+    let (count, limit) = match instruction {
+        Instruction::NPUSHB(values) => (values.len(), 0..=255),
+        Instruction::NPUSHW(values) => (values.len(), 0..=255),
+        Instruction::PUSHB(values) => (values.len(), 1..=8),
+        Instruction::PUSHW(values) => (values.len(), 1..=8),
+        _ => return Ok(0),
+    };
+    if !limit.contains(&count) {
+        raise!(
+            "found a wrong number of operands of {} ({count})",
+            instruction.name(),
+        );
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{assemble, decode, disassemble, encode, Instruction};
+
+    macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+    #[test]
+    fn round_trip() {
+        let data = [
+            0x40, 0x02, 0x01, 0x02, 0xB9, 0xFF, 0xFE, 0x00, 0x03, 0x58, 0x00, 0x1B, 0xED, 0x59,
+            0x28,
+        ];
+        let instructions = ok!(decode(&data));
+        assert_eq!(
+            instructions,
+            [
+                Instruction::NPUSHB(vec![1, 2]),
+                Instruction::PUSHW(vec![-2, 3]),
+                Instruction::IF,
+                Instruction::SVTCA(0),
+                Instruction::ELSE,
+                Instruction::MIRP(0b01101),
+                Instruction::EIF,
+                Instruction::Unknown(0x28),
+            ],
+        );
+        assert_eq!(ok!(encode(&instructions)), data);
+        let text = ok!(disassemble(&data));
+        assert_eq!(
+            text,
+            "NPUSHB[] 1 2\nPUSHW[001] -2 3\nIF[]\n  SVTCA[0]\nELSE[]\n  MIRP[01101]\nEIF[]\nUNKNOWN[0x28]\n",
+        );
+        assert_eq!(ok!(assemble(&text)), data);
+    }
+
+    #[test]
+    fn malformed() {
+        assert!(decode(&[0x40, 0x02, 0x01]).is_err());
+        assert!(decode(&[0xB8, 0x01]).is_err());
+        assert!(decode(&[0x58]).is_err());
+        assert!(decode(&[0x59]).is_err());
+        assert!(decode(&[0x1B]).is_err());
+        assert!(assemble("PUSHB[001] 1").is_err());
+        assert!(assemble("PUSHB[000] 256").is_err());
+        assert!(assemble("MIRP[0110]").is_err());
+        assert!(assemble("DUP[] 1").is_err());
+        assert!(assemble("FOO[]").is_err());
+        assert!(encode(&[Instruction::SVTCA(2)]).is_err());
+        assert!(encode(&[Instruction::PUSHB(vec![])]).is_err());
+        assert!(encode(&[Instruction::PUSHW(vec![0; 9])]).is_err());
+        assert!(encode(&[Instruction::PUSHB(vec![0; 257])]).is_err());
+        assert!(encode(&[Instruction::NPUSHW(vec![0; 256])]).is_err());
+    }
+
+    #[test]
+    fn malformed_display() {
+        assert_eq!(Instruction::PUSHB(vec![]).to_string(), "PUSHB[]");
+        assert_eq!(
+            Instruction::PUSHW(vec![1; 9]).to_string(),
+            "PUSHW[] 1 1 1 1 1 1 1 1 1",
+        );
+        assert!(assemble(&Instruction::PUSHB(vec![]).to_string()).is_err());
+        assert_eq!(
+            Instruction::PUSHB(vec![7; 8]).to_string(),
+            "PUSHB[111] 7 7 7 7 7 7 7 7"
+        );
+    }
+}
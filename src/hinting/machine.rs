@@ -0,0 +1,1511 @@
+//! The [virtual machine][1].
+//!
+//! The font program is executed once when a machine is created, the
+//! control-value program once for each size, and the program of a glyph each
+//! time the glyph is grid-fitted. Coordinates are kept in 26.6 fixed-point
+//! pixels, and vectors in 2.14 fixed-point units.
+//!
+//! The resources available to programs, that is, the number of twilight points,
+//! storage locations, function and instruction definitions, and stack
+//! elements, are limited by the maximum profile. The stack is given some
+//! headroom over the declared maximum, as fonts often underestimate it. In
+//! addition, the depth of calls and the number of instructions executed by a
+//! program are limited in order to guard against hostile fonts, and arithmetic
+//! on values taken from programs wraps around instead of overflowing.
+//!
+//! The resolution of the device is not known, so `MPS` pushes the size in
+//! pixels per em like `MPPEM`, which is the point size at 72 pixels per inch.
+//! `INSTCTRL` takes effect only in the control-value program and is otherwise
+//! ignored after popping its arguments.
+//!
+//! [1]: https://learn.microsoft.com/en-us/typography/opentype/spec/tt_instructing_glyphs
+
+use std::rc::Rc;
+
+use crate::hinting::instructions::{self, Instruction};
+use crate::outline::{Contour, Outline, Point};
+use crate::tables::maximum_profile::MaximumProfile;
+use crate::{Font, Result, Tag};
+
+const MAX_DEPTH: usize = 64;
+const MAX_STEPS: usize = 1_000_000;
+const PHANTOM_COUNT: usize = 4;
+const STACK_HEADROOM: usize = 32;
+const ONE: i32 = 0x4000;
+
+/// A virtual machine.
+#[derive(Clone, Debug)]
+pub struct Machine {
+    units_per_em: i64,
+    ppem: u16,
+    limits: Limits,
+    control_values: Vec<i16>,
+    control_value_program: Rc<Program>,
+    functions: Vec<Option<Function>>,
+    definitions: Vec<(u8, Function)>,
+    prepared: Option<Prepared>,
+
+    cvt: Vec<i32>,
+    storage: Vec<i32>,
+    stack: Vec<i32>,
+    state: State,
+    zones: [Zone; 2],
+    control: i32,
+    preparing: bool,
+    steps: usize,
+}
+
+/// A grid-fitted glyph.
+#[derive(Clone, Debug)]
+pub struct Hinted {
+    /// The outline in pixels.
+    pub outline: Outline,
+    /// The advance width in pixels.
+    pub advance_width: f32,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Limits {
+    twilight_points: usize,
+    storage: usize,
+    functions: usize,
+    definitions: usize,
+    stack: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Program {
+    instructions: Vec<Instruction>,
+    offsets: Vec<usize>,
+}
+
+#[derive(Clone, Debug)]
+struct Function {
+    program: Rc<Program>,
+    start: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Prepared {
+    cvt: Vec<i32>,
+    storage: Vec<i32>,
+    state: State,
+    control: i32,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct State {
+    projection: (i32, i32),
+    freedom: (i32, i32),
+    dual: (i32, i32),
+    references: [usize; 3],
+    zones: [usize; 3],
+    count: i32,
+    rounding: Rounding,
+    min_distance: i32,
+    cut_in: i32,
+    single_width_cut_in: i32,
+    single_width: i32,
+    delta_base: i32,
+    delta_shift: i32,
+    auto_flip: bool,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Rounding {
+    Grid,
+    HalfGrid,
+    DoubleGrid,
+    DownToGrid,
+    UpToGrid,
+    Off,
+    Super {
+        period: i32,
+        phase: i32,
+        threshold: i32,
+    },
+}
+
+#[derive(Clone, Debug, Default)]
+struct Zone {
+    original: [Vec<i32>; 2],
+    current: [Vec<i32>; 2],
+    touched: [Vec<bool>; 2],
+    on_curve: Vec<bool>,
+    ends: Vec<usize>,
+}
+
+impl Machine {
+    /// Create an instance and execute the font program.
+    pub fn new(
+        profile: &MaximumProfile,
+        units_per_em: u16,
+        font_program: &[u8],
+        control_values: &[i16],
+        control_value_program: &[u8],
+    ) -> Result<Self> {
+        let profile = match profile {
+            MaximumProfile::Version1(profile) => profile,
+            _ => raise!("found no limits for hinting in the maximum profile"),
+        };
+        if units_per_em == 0 {
+            raise!("found a malformed number of units per em");
+        }
+        let limits = Limits {
+            twilight_points: profile.max_twilight_points as usize,
+            storage: profile.max_storage as usize,
+            functions: profile.max_function_definitions as usize,
+            definitions: profile.max_instruction_definitions as usize,
+            stack: profile.max_stack_elements as usize + STACK_HEADROOM,
+        };
+        let mut machine = Self {
+            units_per_em: units_per_em as i64,
+            ppem: 0,
+            limits,
+            control_values: control_values.to_vec(),
+            control_value_program: Rc::new(Program::new(control_value_program)?),
+            functions: vec![None; limits.functions],
+            definitions: vec![],
+            prepared: None,
+            cvt: vec![0; control_values.len()],
+            storage: vec![0; limits.storage],
+            stack: Vec::with_capacity(limits.stack),
+            state: State::default(),
+            zones: [Zone::new(limits.twilight_points), Zone::default()],
+            control: 0,
+            preparing: false,
+            steps: 0,
+        };
+        let program = Rc::new(Program::new(font_program)?);
+        machine.execute(&program, 0, 0)?;
+        Ok(machine)
+    }
+
+    /// Create an instance using the tables of a font and execute the font
+    /// program.
+    pub fn from_font<T: crate::tape::Read>(font: &mut Font<T>) -> Result<Self> {
+        let units_per_em = font.font_header()?.units_per_em;
+        let profile = font.maximum_profile()?.clone();
+        let font_program = match font.contains(Tag(*b"fpgm")) {
+            true => font.font_program()?.to_vec(),
+            _ => vec![],
+        };
+        let control_values = match font.contains(Tag(*b"cvt ")) {
+            true => font.control_values()?.to_vec(),
+            _ => vec![],
+        };
+        let control_value_program = match font.contains(Tag(*b"prep")) {
+            true => font.control_value_program()?.to_vec(),
+            _ => vec![],
+        };
+        Self::new(
+            &profile,
+            units_per_em,
+            &font_program,
+            &control_values,
+            &control_value_program,
+        )
+    }
+
+    /// Return the size in pixels per em.
+    #[inline]
+    pub fn ppem(&self) -> u16 {
+        self.ppem
+    }
+
+    /// Set the size in pixels per em and execute the control-value program.
+    pub fn set_ppem(&mut self, ppem: u16) -> Result<()> {
+        if ppem == 0 {
+            raise!("found a malformed number of pixels per em");
+        }
+        self.ppem = ppem;
+        self.prepared = None;
+        self.cvt = self
+            .control_values
+            .iter()
+            .map(|&value| self.scale(value as i32))
+            .collect();
+        self.state = State::default();
+        self.zones = [Zone::new(self.limits.twilight_points), Zone::default()];
+        self.control = 0;
+        let program = self.control_value_program.clone();
+        self.preparing = true;
+        let result = self.execute(&program, 0, 0);
+        self.preparing = false;
+        result?;
+        self.prepared = Some(Prepared {
+            cvt: self.cvt.clone(),
+            storage: self.storage.clone(),
+            state: State {
+                references: [0; 3],
+                zones: [1; 3],
+                count: 1,
+                ..self.state
+            },
+            control: self.control,
+        });
+        Ok(())
+    }
+
+    /// Grid-fit an outline in font units given the instructions, the advance
+    /// width, and the left side bearing of the glyph.
+    ///
+    /// The horizontal origin is placed at the grid-fitted position of the
+    /// first phantom point. The outline is hinted as a whole, so a composite
+    /// glyph is hinted as its flattened outline with the instructions of the
+    /// composite itself, and the instructions of its components are not
+    /// executed.
+    pub fn hint(
+        &mut self,
+        outline: &Outline,
+        instructions: &[u8],
+        advance_width: u16,
+        left_side_bearing: i16,
+    ) -> Result<Hinted> {
+        let prepared = match &self.prepared {
+            Some(prepared) => prepared.clone(),
+            _ => raise!("found no size to hint for"),
+        };
+        self.cvt = prepared.cvt;
+        self.storage = prepared.storage;
+        self.state = prepared.state;
+        self.control = prepared.control;
+
+        let mut zone = Zone::default();
+        for contour in outline.iter() {
+            for point in contour.iter() {
+                let x = self.scale(point.x.round() as i32);
+                let y = self.scale(point.y.round() as i32);
+                zone.push(x, y, point.on_curve);
+            }
+            zone.ends.push(zone.on_curve.len());
+        }
+        let min_x = outline.bounds().map(|bounds| bounds.0).unwrap_or(0.0);
+        let left = round(self.scale((min_x.round() as i32).wrapping_sub(left_side_bearing as i32)));
+        let right = left.wrapping_add(round(self.scale(advance_width as i32)));
+        zone.push(left, 0, true);
+        zone.push(right, 0, true);
+        zone.push(0, 0, true);
+        zone.push(0, 0, true);
+        self.zones = [Zone::new(self.limits.twilight_points), zone];
+
+        if !instructions.is_empty() && self.control & 1 == 0 {
+            let program = Rc::new(Program::new(instructions)?);
+            self.execute(&program, 0, 0)?;
+        }
+
+        let zone = &self.zones[1];
+        let count = zone.on_curve.len() - PHANTOM_COUNT;
+        let origin = zone.current[0][count];
+        let mut contours = Vec::with_capacity(zone.ends.len());
+        let mut start = 0;
+        for &end in zone.ends.iter() {
+            let points = (start..end)
+                .map(|i| Point {
+                    x: zone.current[0][i].wrapping_sub(origin) as f32 / 64.0,
+                    y: zone.current[1][i] as f32 / 64.0,
+                    on_curve: zone.on_curve[i],
+                })
+                .collect();
+            contours.push(Contour(points));
+            start = end;
+        }
+        Ok(Hinted {
+            outline: Outline(contours),
+            advance_width: zone.current[0][count + 1].wrapping_sub(origin) as f32 / 64.0,
+        })
+    }
+
+    fn execute(&mut self, program: &Rc<Program>, start: usize, depth: usize) -> Result<()> {
+        if depth == 0 {
+            self.stack.clear();
+            self.steps = 0;
+        }
+        let mut i = start;
+        while let Some(instruction) = program.instructions.get(i) {
+            self.steps += 1;
+            if self.steps > MAX_STEPS {
+                raise!("found too many instructions to execute");
+            }
+            let mut next = i + 1;
+            match instruction {
+                Instruction::SVTCA(axis) => {
+                    self.state.projection = vector(*axis);
+                    self.state.freedom = vector(*axis);
+                    self.state.dual = vector(*axis);
+                }
+                Instruction::SPVTCA(axis) => {
+                    self.state.projection = vector(*axis);
+                    self.state.dual = vector(*axis);
+                }
+                Instruction::SFVTCA(axis) => self.state.freedom = vector(*axis),
+                Instruction::SPVTL(flag) => {
+                    let (current, _) = self.line(*flag)?;
+                    self.state.projection = current;
+                    self.state.dual = current;
+                }
+                Instruction::SFVTL(flag) => self.state.freedom = self.line(*flag)?.0,
+                Instruction::SDPVTL(flag) => {
+                    let (current, original) = self.line(*flag)?;
+                    self.state.projection = current;
+                    self.state.dual = original;
+                }
+                Instruction::SPVFS => {
+                    let y = self.pop()?;
+                    let x = self.pop()?;
+                    self.state.projection = normalize(x as i64, y as i64);
+                    self.state.dual = self.state.projection;
+                }
+                Instruction::SFVFS => {
+                    let y = self.pop()?;
+                    let x = self.pop()?;
+                    self.state.freedom = normalize(x as i64, y as i64);
+                }
+                Instruction::GPV => {
+                    self.push(self.state.projection.0)?;
+                    self.push(self.state.projection.1)?;
+                }
+                Instruction::GFV => {
+                    self.push(self.state.freedom.0)?;
+                    self.push(self.state.freedom.1)?;
+                }
+                Instruction::SFVTPV => self.state.freedom = self.state.projection,
+                Instruction::ISECT => self.intersect()?,
+                Instruction::SRP0 => self.state.references[0] = self.pop_usize()?,
+                Instruction::SRP1 => self.state.references[1] = self.pop_usize()?,
+                Instruction::SRP2 => self.state.references[2] = self.pop_usize()?,
+                Instruction::SZP0 => self.state.zones[0] = self.pop_zone()?,
+                Instruction::SZP1 => self.state.zones[1] = self.pop_zone()?,
+                Instruction::SZP2 => self.state.zones[2] = self.pop_zone()?,
+                Instruction::SZPS => self.state.zones = [self.pop_zone()?; 3],
+                Instruction::SLOOP => {
+                    self.state.count = self.pop()?;
+                    if self.state.count < 0 {
+                        raise!("found a malformed loop count");
+                    }
+                }
+                Instruction::RTG => self.state.rounding = Rounding::Grid,
+                Instruction::RTHG => self.state.rounding = Rounding::HalfGrid,
+                Instruction::RTDG => self.state.rounding = Rounding::DoubleGrid,
+                Instruction::RDTG => self.state.rounding = Rounding::DownToGrid,
+                Instruction::RUTG => self.state.rounding = Rounding::UpToGrid,
+                Instruction::ROFF => self.state.rounding = Rounding::Off,
+                Instruction::SROUND => self.state.rounding = Rounding::new(self.pop()?, 64),
+                Instruction::S45ROUND => self.state.rounding = Rounding::new(self.pop()?, 45),
+                Instruction::SMD => self.state.min_distance = self.pop()?,
+                Instruction::SCVTCI => self.state.cut_in = self.pop()?,
+                Instruction::SSWCI => self.state.single_width_cut_in = self.pop()?,
+                Instruction::SSW => {
+                    let value = self.pop()?;
+                    self.state.single_width = self.scale(value);
+                }
+                Instruction::IF => {
+                    if self.pop()? == 0 {
+                        next = program.skip(i, true)?;
+                    }
+                }
+                Instruction::ELSE => next = program.skip(i, false)?,
+                Instruction::EIF => {}
+                Instruction::JMPR => {
+                    let offset = self.pop()?;
+                    next = program.jump(i, offset)?;
+                }
+                Instruction::JROT => {
+                    let condition = self.pop()?;
+                    let offset = self.pop()?;
+                    if condition != 0 {
+                        next = program.jump(i, offset)?;
+                    }
+                }
+                Instruction::JROF => {
+                    let condition = self.pop()?;
+                    let offset = self.pop()?;
+                    if condition == 0 {
+                        next = program.jump(i, offset)?;
+                    }
+                }
+                Instruction::DUP => {
+                    let value = self.peek(1)?;
+                    self.push(value)?;
+                }
+                Instruction::POP => {
+                    self.pop()?;
+                }
+                Instruction::CLEAR => self.stack.clear(),
+                Instruction::SWAP => {
+                    let a = self.pop()?;
+                    let b = self.pop()?;
+                    self.push(a)?;
+                    self.push(b)?;
+                }
+                Instruction::DEPTH => self.push(self.stack.len() as i32)?,
+                Instruction::CINDEX => {
+                    let index = self.pop()?;
+                    let value = self.peek(index)?;
+                    self.push(value)?;
+                }
+                Instruction::MINDEX => {
+                    let index = self.pop()?;
+                    let value = self.peek(index)?;
+                    self.stack.remove(self.stack.len() - index as usize);
+                    self.push(value)?;
+                }
+                Instruction::ROLL => {
+                    let a = self.pop()?;
+                    let b = self.pop()?;
+                    let c = self.pop()?;
+                    self.push(b)?;
+                    self.push(a)?;
+                    self.push(c)?;
+                }
+                Instruction::FDEF => {
+                    let index = self.pop_usize()?;
+                    if index >= self.functions.len() {
+                        raise!("found too many function definitions");
+                    }
+                    let end = program.end(i)?;
+                    self.functions[index] = Some(Function {
+                        program: program.clone(),
+                        start: i + 1,
+                    });
+                    next = end + 1;
+                }
+                Instruction::IDEF => {
+                    let opcode = match u8::try_from(self.pop()?) {
+                        Ok(opcode) => opcode,
+                        _ => raise!("found a malformed instruction definition"),
+                    };
+                    let end = program.end(i)?;
+                    let function = Function {
+                        program: program.clone(),
+                        start: i + 1,
+                    };
+                    let position = self
+                        .definitions
+                        .iter()
+                        .position(|(other, _)| *other == opcode);
+                    match position {
+                        Some(position) => self.definitions[position].1 = function,
+                        _ if self.definitions.len() < self.limits.definitions => {
+                            self.definitions.push((opcode, function));
+                        }
+                        _ => raise!("found too many instruction definitions"),
+                    }
+                    next = end + 1;
+                }
+                Instruction::ENDF => {
+                    if depth == 0 {
+                        raise!("found an unexpected end of a function");
+                    }
+                    return Ok(());
+                }
+                Instruction::CALL => {
+                    let function = self.function()?;
+                    self.call(&function, depth)?;
+                }
+                Instruction::LOOPCALL => {
+                    let function = self.function()?;
+                    let count = self.pop()?;
+                    for _ in 0..count.max(0) {
+                        self.call(&function, depth)?;
+                    }
+                }
+                Instruction::MDAP(flag) => {
+                    let point = self.pop_point(0)?;
+                    let distance = if *flag == 1 {
+                        let value = self.coordinate(0, point, false);
+                        self.round(value).wrapping_sub(value)
+                    } else {
+                        0
+                    };
+                    self.shift(0, point, distance);
+                    self.state.references[0] = point;
+                    self.state.references[1] = point;
+                }
+                Instruction::MIAP(flag) => {
+                    let index = self.pop()?;
+                    let point = self.pop_point(0)?;
+                    let mut distance = self.read_cvt(index)?;
+                    if self.state.zones[0] == 0 {
+                        let (x, y) = self.along_freedom(distance);
+                        self.zones[0].set(point, x, y);
+                    }
+                    let value = self.coordinate(0, point, false);
+                    if *flag == 1 {
+                        if distance.wrapping_sub(value).wrapping_abs() > self.state.cut_in {
+                            distance = value;
+                        }
+                        distance = self.round(distance);
+                    }
+                    self.shift(0, point, distance.wrapping_sub(value));
+                    self.state.references[0] = point;
+                    self.state.references[1] = point;
+                }
+                Instruction::MDRP(flags) => self.move_relative(*flags, None)?,
+                Instruction::MIRP(flags) => {
+                    let index = self.pop()?;
+                    self.move_relative(*flags, Some(index))?;
+                }
+                Instruction::MSIRP(flag) => {
+                    let distance = self.pop()?;
+                    let point = self.pop_point(1)?;
+                    let reference = self.reference(0, 0)?;
+                    if self.state.zones[1] == 0 {
+                        let zone = &self.zones[self.state.zones[0]];
+                        let (x, y) = (zone.original[0][reference], zone.original[1][reference]);
+                        self.zones[0].set(point, x, y);
+                    }
+                    let value = self.distance(1, point, 0, reference, false);
+                    self.shift(1, point, distance.wrapping_sub(value));
+                    self.state.references[1] = self.state.references[0];
+                    self.state.references[2] = point;
+                    if *flag == 1 {
+                        self.state.references[0] = point;
+                    }
+                }
+                Instruction::ALIGNRP => {
+                    let reference = self.reference(0, 0)?;
+                    for _ in 0..self.take_count() {
+                        let point = self.pop_point(1)?;
+                        let value = self.distance(1, point, 0, reference, false);
+                        self.shift(1, point, value.wrapping_neg());
+                    }
+                }
+                Instruction::ALIGNPTS => {
+                    let second = self.pop_point(0)?;
+                    let first = self.pop_point(1)?;
+                    let value = self.distance(0, second, 1, first, false) / 2;
+                    self.shift(1, first, value);
+                    self.shift(0, second, -value);
+                }
+                Instruction::IP => self.interpolate()?,
+                Instruction::IUP(axis) => self.interpolate_untouched(*axis as usize),
+                Instruction::SHP(flag) => {
+                    let (_, _, x, y) = self.displacement(*flag)?;
+                    for _ in 0..self.take_count() {
+                        let point = self.pop_point(2)?;
+                        self.translate(self.state.zones[2], point, x, y, true);
+                    }
+                }
+                Instruction::SHC(flag) => {
+                    let (zone, reference, x, y) = self.displacement(*flag)?;
+                    let contour = self.pop_usize()?;
+                    let target = self.state.zones[2];
+                    let ends = &self.zones[target].ends;
+                    let end = match ends.get(contour) {
+                        Some(&end) => end,
+                        _ => raise!("found a reference to a nonexistent contour"),
+                    };
+                    let start = if contour == 0 { 0 } else { ends[contour - 1] };
+                    for point in start..end {
+                        if zone != target || point != reference {
+                            self.translate(target, point, x, y, true);
+                        }
+                    }
+                }
+                Instruction::SHZ(flag) => {
+                    let (zone, reference, x, y) = self.displacement(*flag)?;
+                    let target = self.pop_zone()?;
+                    let count = match target {
+                        0 => self.zones[0].on_curve.len(),
+                        _ => self.zones[1].on_curve.len().saturating_sub(PHANTOM_COUNT),
+                    };
+                    for point in 0..count {
+                        if zone != target || point != reference {
+                            self.translate(target, point, x, y, false);
+                        }
+                    }
+                }
+                Instruction::SHPIX => {
+                    let distance = self.pop()?;
+                    let x = (distance as i64 * self.state.freedom.0 as i64 / ONE as i64) as i32;
+                    let y = (distance as i64 * self.state.freedom.1 as i64 / ONE as i64) as i32;
+                    for _ in 0..self.take_count() {
+                        let point = self.pop_point(2)?;
+                        self.translate(self.state.zones[2], point, x, y, true);
+                    }
+                }
+                Instruction::UTP => {
+                    let point = self.pop_point(0)?;
+                    let zone = &mut self.zones[self.state.zones[0]];
+                    if self.state.freedom.0 != 0 {
+                        zone.touched[0][point] = false;
+                    }
+                    if self.state.freedom.1 != 0 {
+                        zone.touched[1][point] = false;
+                    }
+                }
+                Instruction::SCFS => {
+                    let value = self.pop()?;
+                    let point = self.pop_point(2)?;
+                    let current = self.coordinate(2, point, false);
+                    self.shift(2, point, value.wrapping_sub(current));
+                    if self.state.zones[2] == 0 {
+                        let zone = &mut self.zones[0];
+                        for axis in 0..2 {
+                            zone.original[axis][point] = zone.current[axis][point];
+                        }
+                    }
+                }
+                Instruction::GC(flag) => {
+                    let point = self.pop_point(2)?;
+                    let value = self.coordinate(2, point, *flag == 1);
+                    self.push(value)?;
+                }
+                Instruction::MD(flag) => {
+                    let second = self.pop_point(1)?;
+                    let first = self.pop_point(0)?;
+                    let value = self.distance(0, first, 1, second, *flag == 1);
+                    self.push(value)?;
+                }
+                Instruction::FLIPPT => {
+                    for _ in 0..self.take_count() {
+                        let point = self.pop_glyph_point()?;
+                        let zone = &mut self.zones[1];
+                        zone.on_curve[point] = !zone.on_curve[point];
+                    }
+                }
+                Instruction::FLIPRGON | Instruction::FLIPRGOFF => {
+                    let last = self.pop_glyph_point()?;
+                    let first = self.pop_glyph_point()?;
+                    let value = matches!(instruction, Instruction::FLIPRGON);
+                    for point in first..=last {
+                        self.zones[1].on_curve[point] = value;
+                    }
+                }
+                Instruction::NPUSHB(values) | Instruction::PUSHB(values) => {
+                    for &value in values.iter() {
+                        self.push(value as i32)?;
+                    }
+                }
+                Instruction::NPUSHW(values) | Instruction::PUSHW(values) => {
+                    for &value in values.iter() {
+                        self.push(value as i32)?;
+                    }
+                }
+                Instruction::WS => {
+                    let value = self.pop()?;
+                    let index = self.pop_usize()?;
+                    match self.storage.get_mut(index) {
+                        Some(location) => *location = value,
+                        _ => raise!("found a reference to a nonexistent storage location"),
+                    }
+                }
+                Instruction::RS => {
+                    let index = self.pop_usize()?;
+                    match self.storage.get(index) {
+                        Some(&value) => self.push(value)?,
+                        _ => raise!("found a reference to a nonexistent storage location"),
+                    }
+                }
+                Instruction::WCVTP | Instruction::WCVTF => {
+                    let mut value = self.pop()?;
+                    let index = self.pop()?;
+                    if matches!(instruction, Instruction::WCVTF) {
+                        value = self.scale(value);
+                    }
+                    self.write_cvt(index, value)?;
+                }
+                Instruction::RCVT => {
+                    let index = self.pop()?;
+                    let value = self.read_cvt(index)?;
+                    self.push(value)?;
+                }
+                Instruction::MPPEM | Instruction::MPS => self.push(self.ppem as i32)?,
+                Instruction::FLIPON => self.state.auto_flip = true,
+                Instruction::FLIPOFF => self.state.auto_flip = false,
+                Instruction::DEBUG
+                | Instruction::SANGW
+                | Instruction::AA
+                | Instruction::SCANCTRL
+                | Instruction::SCANTYPE => {
+                    self.pop()?;
+                }
+                Instruction::LT => self.compare(|a, b| a < b)?,
+                Instruction::LTEQ => self.compare(|a, b| a <= b)?,
+                Instruction::GT => self.compare(|a, b| a > b)?,
+                Instruction::GTEQ => self.compare(|a, b| a >= b)?,
+                Instruction::EQ => self.compare(|a, b| a == b)?,
+                Instruction::NEQ => self.compare(|a, b| a != b)?,
+                Instruction::AND => self.compare(|a, b| a != 0 && b != 0)?,
+                Instruction::OR => self.compare(|a, b| a != 0 || b != 0)?,
+                Instruction::ODD | Instruction::EVEN => {
+                    let value = self.pop()?;
+                    let value = self.round(value) & 127;
+                    let expected = if matches!(instruction, Instruction::ODD) {
+                        64
+                    } else {
+                        0
+                    };
+                    self.push((value == expected) as i32)?;
+                }
+                Instruction::NOT => {
+                    let value = self.pop()?;
+                    self.push((value == 0) as i32)?;
+                }
+                Instruction::DELTAP1 => self.delta_points(0)?,
+                Instruction::DELTAP2 => self.delta_points(16)?,
+                Instruction::DELTAP3 => self.delta_points(32)?,
+                Instruction::DELTAC1 => self.delta_values(0)?,
+                Instruction::DELTAC2 => self.delta_values(16)?,
+                Instruction::DELTAC3 => self.delta_values(32)?,
+                Instruction::SDB => self.state.delta_base = self.pop()?,
+                Instruction::SDS => {
+                    self.state.delta_shift = self.pop()?;
+                    if !(0..=6).contains(&self.state.delta_shift) {
+                        raise!("found a malformed delta shift");
+                    }
+                }
+                Instruction::ADD => self.arithmetic(|a, b| Some(a + b))?,
+                Instruction::SUB => self.arithmetic(|a, b| Some(a - b))?,
+                Instruction::DIV => self.arithmetic(|a, b| (b != 0).then(|| a * 64 / b))?,
+                Instruction::MUL => self.arithmetic(|a, b| Some(divide(a * b, 64)))?,
+                Instruction::MAX => self.arithmetic(|a, b| Some(a.max(b)))?,
+                Instruction::MIN => self.arithmetic(|a, b| Some(a.min(b)))?,
+                Instruction::ABS => {
+                    let value = self.pop()?;
+                    self.push(value.wrapping_abs())?;
+                }
+                Instruction::NEG => {
+                    let value = self.pop()?;
+                    self.push(value.wrapping_neg())?;
+                }
+                Instruction::FLOOR => {
+                    let value = self.pop()?;
+                    self.push(value & !63)?;
+                }
+                Instruction::CEILING => {
+                    let value = self.pop()?;
+                    self.push(value.wrapping_add(63) & !63)?;
+                }
+                Instruction::ROUND(_) => {
+                    let value = self.pop()?;
+                    self.push(self.round(value))?;
+                }
+                Instruction::NROUND(_) => {}
+                Instruction::GETINFO => {
+                    let selector = self.pop()?;
+                    self.push(if selector & 1 != 0 { 35 } else { 0 })?;
+                }
+                Instruction::INSTCTRL => {
+                    let selector = self.pop()?;
+                    let value = self.pop()?;
+                    if self.preparing && (1..=3).contains(&selector) {
+                        let mask = 1 << (selector - 1);
+                        self.control = (self.control & !mask) | (value & mask);
+                    }
+                }
+                Instruction::GETDATA => self.push(17)?,
+                Instruction::GETVARIATION => {
+                    raise!("found an unsupported instruction GETVARIATION")
+                }
+                Instruction::Unknown(opcode) => {
+                    let function = match self.definitions.iter().find(|(other, _)| other == opcode)
+                    {
+                        Some((_, function)) => function.clone(),
+                        _ => raise!("found an unknown instruction with opcode {opcode:#04x}"),
+                    };
+                    self.call(&function, depth)?;
+                }
+            }
+            i = next;
+        }
+        if depth > 0 {
+            raise!("found a function without an end");
+        }
+        Ok(())
+    }
+
+    fn call(&mut self, function: &Function, depth: usize) -> Result<()> {
+        if depth + 1 > MAX_DEPTH {
+            raise!("found too many nested function calls");
+        }
+        self.execute(&function.program, function.start, depth + 1)
+    }
+
+    fn function(&mut self) -> Result<Function> {
+        let index = self.pop_usize()?;
+        match self.functions.get(index) {
+            Some(Some(function)) => Ok(function.clone()),
+            _ => raise!("found a call to an undefined function {index}"),
+        }
+    }
+
+    fn push(&mut self, value: i32) -> Result<()> {
+        if self.stack.len() >= self.limits.stack {
+            raise!("found a stack overflow");
+        }
+        self.stack.push(value);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Result<i32> {
+        match self.stack.pop() {
+            Some(value) => Ok(value),
+            _ => raise!("found a stack underflow"),
+        }
+    }
+
+    fn peek(&self, index: i32) -> Result<i32> {
+        if index < 1 || index as usize > self.stack.len() {
+            raise!("found a reference to a nonexistent stack element");
+        }
+        Ok(self.stack[self.stack.len() - index as usize])
+    }
+
+    fn pop_usize(&mut self) -> Result<usize> {
+        let value = self.pop()?;
+        if value < 0 {
+            raise!("found a malformed index");
+        }
+        Ok(value as usize)
+    }
+
+    fn pop_zone(&mut self) -> Result<usize> {
+        match self.pop()? {
+            value @ (0 | 1) => Ok(value as usize),
+            _ => raise!("found a reference to a nonexistent zone"),
+        }
+    }
+
+    fn pop_point(&mut self, pointer: usize) -> Result<usize> {
+        let point = self.pop()?;
+        self.check(pointer, point)
+    }
+
+    fn pop_glyph_point(&mut self) -> Result<usize> {
+        let point = self.pop()?;
+        if point < 0 || point as usize >= self.zones[1].on_curve.len() {
+            raise!("found a reference to a nonexistent point");
+        }
+        Ok(point as usize)
+    }
+
+    fn check(&self, pointer: usize, point: i32) -> Result<usize> {
+        let zone = &self.zones[self.state.zones[pointer]];
+        if point < 0 || point as usize >= zone.on_curve.len() {
+            raise!("found a reference to a nonexistent point");
+        }
+        Ok(point as usize)
+    }
+
+    fn reference(&self, index: usize, pointer: usize) -> Result<usize> {
+        self.check(pointer, self.state.references[index] as i32)
+    }
+
+    fn take_count(&mut self) -> i32 {
+        std::mem::replace(&mut self.state.count, 1)
+    }
+
+    fn compare<F: Fn(i32, i32) -> bool>(&mut self, function: F) -> Result<()> {
+        let b = self.pop()?;
+        let a = self.pop()?;
+        self.push(function(a, b) as i32)
+    }
+
+    fn arithmetic<F: Fn(i64, i64) -> Option<i64>>(&mut self, function: F) -> Result<()> {
+        let b = self.pop()? as i64;
+        let a = self.pop()? as i64;
+        match function(a, b) {
+            Some(value) => self.push(value as i32),
+            _ => raise!("found a division by zero"),
+        }
+    }
+
+    fn read_cvt(&self, index: i32) -> Result<i32> {
+        match usize::try_from(index)
+            .ok()
+            .and_then(|index| self.cvt.get(index))
+        {
+            Some(&value) => Ok(value),
+            _ => raise!("found a reference to a nonexistent control value"),
+        }
+    }
+
+    fn write_cvt(&mut self, index: i32, value: i32) -> Result<()> {
+        match usize::try_from(index)
+            .ok()
+            .and_then(|index| self.cvt.get_mut(index))
+        {
+            Some(location) => *location = value,
+            _ => raise!("found a reference to a nonexistent control value"),
+        }
+        Ok(())
+    }
+
+    fn scale(&self, value: i32) -> i32 {
+        divide(value as i64 * self.ppem as i64 * 64, self.units_per_em) as i32
+    }
+
+    fn round(&self, value: i32) -> i32 {
+        self.state.rounding.apply(value)
+    }
+
+    fn line(&mut self, flag: u8) -> Result<((i32, i32), (i32, i32))> {
+        let second = self.pop_point(2)?;
+        let first = self.pop_point(1)?;
+        let (a, b) = (
+            &self.zones[self.state.zones[1]],
+            &self.zones[self.state.zones[2]],
+        );
+        let mut current = (
+            a.current[0][first] as i64 - b.current[0][second] as i64,
+            a.current[1][first] as i64 - b.current[1][second] as i64,
+        );
+        let mut original = (
+            a.original[0][first] as i64 - b.original[0][second] as i64,
+            a.original[1][first] as i64 - b.original[1][second] as i64,
+        );
+        if flag == 1 {
+            current = (-current.1, current.0);
+            original = (-original.1, original.0);
+        }
+        Ok((
+            normalize(current.0, current.1),
+            normalize(original.0, original.1),
+        ))
+    }
+
+    fn coordinate(&self, pointer: usize, point: usize, original: bool) -> i32 {
+        let zone = &self.zones[self.state.zones[pointer]];
+        match original {
+            true => project(
+                self.state.dual,
+                zone.original[0][point],
+                zone.original[1][point],
+            ),
+            _ => project(
+                self.state.projection,
+                zone.current[0][point],
+                zone.current[1][point],
+            ),
+        }
+    }
+
+    fn distance(
+        &self,
+        pointer: usize,
+        point: usize,
+        other_pointer: usize,
+        other_point: usize,
+        original: bool,
+    ) -> i32 {
+        let a = &self.zones[self.state.zones[pointer]];
+        let b = &self.zones[self.state.zones[other_pointer]];
+        match original {
+            true => project(
+                self.state.dual,
+                a.original[0][point].wrapping_sub(b.original[0][other_point]),
+                a.original[1][point].wrapping_sub(b.original[1][other_point]),
+            ),
+            _ => project(
+                self.state.projection,
+                a.current[0][point].wrapping_sub(b.current[0][other_point]),
+                a.current[1][point].wrapping_sub(b.current[1][other_point]),
+            ),
+        }
+    }
+
+    fn along_freedom(&self, distance: i32) -> (i32, i32) {
+        (
+            (distance as i64 * self.state.freedom.0 as i64 / ONE as i64) as i32,
+            (distance as i64 * self.state.freedom.1 as i64 / ONE as i64) as i32,
+        )
+    }
+
+    fn dot(&self) -> i64 {
+        let (projection, freedom) = (self.state.projection, self.state.freedom);
+        let value =
+            (projection.0 as i64 * freedom.0 as i64 + projection.1 as i64 * freedom.1 as i64) >> 14;
+        if value.abs() < ONE as i64 / 16 {
+            ONE as i64
+        } else {
+            value
+        }
+    }
+
+    fn shift(&mut self, pointer: usize, point: usize, distance: i32) {
+        let dot = self.dot();
+        let (freedom, zone) = (self.state.freedom, self.state.zones[pointer]);
+        let zone = &mut self.zones[zone];
+        if freedom.0 != 0 {
+            let value = (distance as i64 * freedom.0 as i64 / dot) as i32;
+            zone.current[0][point] = zone.current[0][point].wrapping_add(value);
+            zone.touched[0][point] = true;
+        }
+        if freedom.1 != 0 {
+            let value = (distance as i64 * freedom.1 as i64 / dot) as i32;
+            zone.current[1][point] = zone.current[1][point].wrapping_add(value);
+            zone.touched[1][point] = true;
+        }
+    }
+
+    fn translate(&mut self, zone: usize, point: usize, x: i32, y: i32, touch: bool) {
+        let freedom = self.state.freedom;
+        let zone = &mut self.zones[zone];
+        if freedom.0 != 0 {
+            zone.current[0][point] = zone.current[0][point].wrapping_add(x);
+            zone.touched[0][point] |= touch;
+        }
+        if freedom.1 != 0 {
+            zone.current[1][point] = zone.current[1][point].wrapping_add(y);
+            zone.touched[1][point] |= touch;
+        }
+    }
+
+    fn displacement(&self, flag: u8) -> Result<(usize, usize, i32, i32)> {
+        let (index, pointer) = if flag == 1 { (1, 0) } else { (2, 1) };
+        let point = self.reference(index, pointer)?;
+        let zone = self.state.zones[pointer];
+        let value = {
+            let zone = &self.zones[zone];
+            project(
+                self.state.projection,
+                zone.current[0][point].wrapping_sub(zone.original[0][point]),
+                zone.current[1][point].wrapping_sub(zone.original[1][point]),
+            )
+        };
+        let dot = self.dot();
+        let x = (value as i64 * self.state.freedom.0 as i64 / dot) as i32;
+        let y = (value as i64 * self.state.freedom.1 as i64 / dot) as i32;
+        Ok((zone, point, x, y))
+    }
+
+    fn move_relative(&mut self, flags: u8, index: Option<i32>) -> Result<()> {
+        let point = self.pop_point(1)?;
+        let reference = self.reference(0, 0)?;
+        let (width, cut_in) = (self.state.single_width, self.state.single_width_cut_in);
+        let single_width = |value: i32| {
+            if value.wrapping_sub(width).wrapping_abs() < cut_in {
+                if value >= 0 {
+                    width
+                } else {
+                    width.wrapping_neg()
+                }
+            } else {
+                value
+            }
+        };
+        let distance = match index {
+            Some(index) => {
+                let mut value = single_width(self.read_cvt(index)?);
+                if self.state.zones[1] == 0 {
+                    let zone = &self.zones[self.state.zones[0]];
+                    let (x, y) = self.along_freedom(value);
+                    let (x, y) = (
+                        zone.original[0][reference].wrapping_add(x),
+                        zone.original[1][reference].wrapping_add(y),
+                    );
+                    self.zones[0].set(point, x, y);
+                }
+                let original = self.distance(1, point, 0, reference, true);
+                if self.state.auto_flip && (original ^ value) < 0 {
+                    value = value.wrapping_neg();
+                }
+                let mut value = if flags & 0b00100 != 0 {
+                    if self.state.zones[0] == self.state.zones[1]
+                        && value.wrapping_sub(original).wrapping_abs() > self.state.cut_in
+                    {
+                        value = original;
+                    }
+                    self.round(value)
+                } else {
+                    value
+                };
+                if flags & 0b01000 != 0 {
+                    value = self.limit(value, original);
+                }
+                value
+            }
+            _ => {
+                let original = single_width(self.distance(1, point, 0, reference, true));
+                let mut value = match flags & 0b00100 != 0 {
+                    true => self.round(original),
+                    _ => original,
+                };
+                if flags & 0b01000 != 0 {
+                    value = self.limit(value, original);
+                }
+                value
+            }
+        };
+        let current = self.distance(1, point, 0, reference, false);
+        self.shift(1, point, distance.wrapping_sub(current));
+        self.state.references[1] = self.state.references[0];
+        self.state.references[2] = point;
+        if flags & 0b10000 != 0 {
+            self.state.references[0] = point;
+        }
+        Ok(())
+    }
+
+    fn limit(&self, value: i32, original: i32) -> i32 {
+        if original >= 0 {
+            value.max(self.state.min_distance)
+        } else {
+            value.min(self.state.min_distance.wrapping_neg())
+        }
+    }
+
+    fn intersect(&mut self) -> Result<()> {
+        let b1 = self.pop_point(0)?;
+        let b0 = self.pop_point(0)?;
+        let a1 = self.pop_point(1)?;
+        let a0 = self.pop_point(1)?;
+        let point = self.pop_point(2)?;
+        let get = |pointer: usize, point: usize| {
+            let zone = &self.zones[self.state.zones[pointer]];
+            (zone.current[0][point] as f64, zone.current[1][point] as f64)
+        };
+        let (a0, a1, b0, b1) = (get(1, a0), get(1, a1), get(0, b0), get(0, b1));
+        let (da, db) = ((a1.0 - a0.0, a1.1 - a0.1), (b1.0 - b0.0, b1.1 - b0.1));
+        let denominator = da.0 * db.1 - da.1 * db.0;
+        let (x, y) = if denominator == 0.0 {
+            (
+                (a0.0 + a1.0 + b0.0 + b1.0) / 4.0,
+                (a0.1 + a1.1 + b0.1 + b1.1) / 4.0,
+            )
+        } else {
+            let t = ((b0.0 - a0.0) * db.1 - (b0.1 - a0.1) * db.0) / denominator;
+            (a0.0 + t * da.0, a0.1 + t * da.1)
+        };
+        let zone = &mut self.zones[self.state.zones[2]];
+        zone.current[0][point] = x.round() as i32;
+        zone.current[1][point] = y.round() as i32;
+        zone.touched[0][point] = true;
+        zone.touched[1][point] = true;
+        Ok(())
+    }
+
+    fn interpolate(&mut self) -> Result<()> {
+        let first = self.reference(1, 0)?;
+        let second = self.reference(2, 1)?;
+        let original_range = self.distance(1, second, 0, first, true) as i64;
+        let current_range = self.distance(1, second, 0, first, false) as i64;
+        for _ in 0..self.take_count() {
+            let point = self.pop_point(2)?;
+            let original = self.distance(2, point, 0, first, true) as i64;
+            let current = self.distance(2, point, 0, first, false) as i64;
+            let target = match original_range {
+                0 => original,
+                _ => divide(original * current_range, original_range),
+            };
+            self.shift(2, point, (target - current) as i32);
+        }
+        Ok(())
+    }
+
+    fn interpolate_untouched(&mut self, axis: usize) {
+        let axis = 1 - axis;
+        let zone = &mut self.zones[1];
+        let mut start = 0;
+        for &end in zone.ends.iter() {
+            interpolate(
+                &zone.original[axis][start..end],
+                &mut zone.current[axis][start..end],
+                &zone.touched[axis][start..end],
+            );
+            start = end;
+        }
+    }
+
+    fn delta_points(&mut self, offset: i32) -> Result<()> {
+        let count = self.pop()?;
+        for _ in 0..count.max(0) {
+            let point = self.pop_point(0)?;
+            let argument = self.pop()?;
+            if let Some(value) = self.delta(argument, offset) {
+                self.shift(0, point, value);
+            }
+        }
+        Ok(())
+    }
+
+    fn delta_values(&mut self, offset: i32) -> Result<()> {
+        let count = self.pop()?;
+        for _ in 0..count.max(0) {
+            let index = self.pop()?;
+            let argument = self.pop()?;
+            if let Some(value) = self.delta(argument, offset) {
+                let value = self.read_cvt(index)?.wrapping_add(value);
+                self.write_cvt(index, value)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn delta(&self, argument: i32, offset: i32) -> Option<i32> {
+        let ppem = self
+            .state
+            .delta_base
+            .wrapping_add(offset)
+            .wrapping_add((argument >> 4) & 15);
+        if ppem != self.ppem as i32 {
+            return None;
+        }
+        let steps = argument & 15;
+        let steps = if steps >= 8 { steps - 7 } else { steps - 8 };
+        Some((steps * 64) >> self.state.delta_shift)
+    }
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            projection: (ONE, 0),
+            freedom: (ONE, 0),
+            dual: (ONE, 0),
+            references: [0; 3],
+            zones: [1; 3],
+            count: 1,
+            rounding: Rounding::Grid,
+            min_distance: 64,
+            cut_in: 68,
+            single_width_cut_in: 0,
+            single_width: 0,
+            delta_base: 9,
+            delta_shift: 3,
+            auto_flip: true,
+        }
+    }
+}
+
+impl Rounding {
+    fn new(selector: i32, grid: i32) -> Self {
+        let period = match (selector >> 6) & 3 {
+            0 => grid / 2,
+            2 => grid * 2,
+            _ => grid,
+        };
+        let phase = match (selector >> 4) & 3 {
+            0 => 0,
+            1 => period / 4,
+            2 => period / 2,
+            _ => period * 3 / 4,
+        };
+        let threshold = match selector & 15 {
+            0 => period - 1,
+            value => (value - 4) * period / 8,
+        };
+        Rounding::Super {
+            period,
+            phase,
+            threshold,
+        }
+    }
+
+    fn apply(&self, value: i32) -> i32 {
+        if let Rounding::Super {
+            period,
+            phase,
+            threshold,
+        } = *self
+        {
+            let round = |value: i32| {
+                let value = value.wrapping_sub(phase).wrapping_add(threshold);
+                value
+                    .div_euclid(period)
+                    .wrapping_mul(period)
+                    .wrapping_add(phase)
+            };
+            return if value >= 0 {
+                round(value).max(phase)
+            } else {
+                round(value.wrapping_neg()).wrapping_neg().min(-phase)
+            };
+        }
+        let magnitude = value.unsigned_abs().min(i32::MAX as u32 - 64) as i32;
+        let magnitude = match self {
+            Rounding::Grid => (magnitude + 32) & !63,
+            Rounding::HalfGrid => (magnitude & !63) + 32,
+            Rounding::DoubleGrid => (magnitude + 16) & !31,
+            Rounding::DownToGrid => magnitude & !63,
+            Rounding::UpToGrid => (magnitude + 63) & !63,
+            _ => magnitude,
+        };
+        if value < 0 {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+}
+
+impl Program {
+    fn new(data: &[u8]) -> Result<Self> {
+        let instructions = instructions::decode(data)?;
+        let mut offsets = Vec::with_capacity(instructions.len() + 1);
+        let mut offset = 0;
+        for instruction in instructions.iter() {
+            offsets.push(offset);
+            offset += match instruction {
+                Instruction::NPUSHB(values) => 2 + values.len(),
+                Instruction::NPUSHW(values) => 2 + 2 * values.len(),
+                Instruction::PUSHB(values) => 1 + values.len(),
+                Instruction::PUSHW(values) => 1 + 2 * values.len(),
+                _ => 1,
+            };
+        }
+        offsets.push(offset);
+        Ok(Self {
+            instructions,
+            offsets,
+        })
+    }
+
+    fn jump(&self, from: usize, offset: i32) -> Result<usize> {
+        let target = self.offsets[from] as i64 + offset as i64;
+        match usize::try_from(target)
+            .ok()
+            .and_then(|target| self.offsets.binary_search(&target).ok())
+        {
+            Some(index) => Ok(index),
+            _ => raise!("found a jump to a malformed position"),
+        }
+    }
+
+    fn skip(&self, from: usize, alternative: bool) -> Result<usize> {
+        let mut level = 0;
+        for (i, instruction) in self.instructions.iter().enumerate().skip(from + 1) {
+            match instruction {
+                Instruction::IF => level += 1,
+                Instruction::ELSE if level == 0 && alternative => return Ok(i + 1),
+                Instruction::EIF if level == 0 => return Ok(i + 1),
+                Instruction::EIF => level -= 1,
+                _ => {}
+            }
+        }
+        raise!("found a conditional block without an end")
+    }
+
+    fn end(&self, from: usize) -> Result<usize> {
+        for (i, instruction) in self.instructions.iter().enumerate().skip(from + 1) {
+            match instruction {
+                Instruction::ENDF => return Ok(i),
+                Instruction::FDEF | Instruction::IDEF => break,
+                _ => {}
+            }
+        }
+        raise!("found a definition without an end")
+    }
+}
+
+impl Zone {
+    fn new(count: usize) -> Self {
+        let mut zone = Zone::default();
+        for _ in 0..count {
+            zone.push(0, 0, true);
+        }
+        zone
+    }
+
+    fn push(&mut self, x: i32, y: i32, on_curve: bool) {
+        for (axis, value) in [x, y].into_iter().enumerate() {
+            self.original[axis].push(value);
+            self.current[axis].push(value);
+            self.touched[axis].push(false);
+        }
+        self.on_curve.push(on_curve);
+    }
+
+    fn set(&mut self, point: usize, x: i32, y: i32) {
+        for (axis, value) in [x, y].into_iter().enumerate() {
+            self.original[axis][point] = value;
+            self.current[axis][point] = value;
+        }
+    }
+}
+
+fn vector(axis: u8) -> (i32, i32) {
+    if axis == 1 {
+        (ONE, 0)
+    } else {
+        (0, ONE)
+    }
+}
+
+fn normalize(x: i64, y: i64) -> (i32, i32) {
+    let length = (x as f64).hypot(y as f64);
+    if length == 0.0 {
+        return (ONE, 0);
+    }
+    (
+        (x as f64 * ONE as f64 / length).round() as i32,
+        (y as f64 * ONE as f64 / length).round() as i32,
+    )
+}
+
+fn project(vector: (i32, i32), x: i32, y: i32) -> i32 {
+    ((x as i64 * vector.0 as i64 + y as i64 * vector.1 as i64 + 0x2000) >> 14) as i32
+}
+
+fn divide(a: i64, b: i64) -> i64 {
+    let value = ((a.unsigned_abs() + b.unsigned_abs() / 2) / b.unsigned_abs()) as i64;
+    if (a < 0) != (b < 0) {
+        value.wrapping_neg()
+    } else {
+        value
+    }
+}
+
+fn round(value: i32) -> i32 {
+    Rounding::Grid.apply(value)
+}
+
+fn interpolate(original: &[i32], current: &mut [i32], touched: &[bool]) {
+    let count = original.len();
+    let first = match touched.iter().position(|&touched| touched) {
+        Some(first) => first,
+        _ => return,
+    };
+    let mut i = first;
+    loop {
+        let mut j = (i + 1) % count;
+        while !touched[j] {
+            j = (j + 1) % count;
+        }
+        let (mut lower, mut upper) = ((original[i], current[i]), (original[j], current[j]));
+        if lower.0 > upper.0 {
+            std::mem::swap(&mut lower, &mut upper);
+        }
+        let mut k = (i + 1) % count;
+        while k != j {
+            let value = original[k] as i64;
+            let (lower, upper) = (
+                (lower.0 as i64, lower.1 as i64),
+                (upper.0 as i64, upper.1 as i64),
+            );
+            current[k] = if value <= lower.0 {
+                value + lower.1 - lower.0
+            } else if value >= upper.0 {
+                value + upper.1 - upper.0
+            } else {
+                let value = (value - lower.0).saturating_mul(upper.1 - lower.1);
+                lower.1.saturating_add(divide(value, upper.0 - lower.0))
+            } as i32;
+            k = (k + 1) % count;
+        }
+        i = j;
+        if i == first {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rounding;
+
+    #[test]
+    fn rounding() {
+        assert_eq!(Rounding::Grid.apply(95), 64);
+        assert_eq!(Rounding::Grid.apply(-96), -128);
+        assert_eq!(Rounding::HalfGrid.apply(10), 32);
+        assert_eq!(Rounding::DoubleGrid.apply(50), 64);
+        assert_eq!(Rounding::DownToGrid.apply(127), 64);
+        assert_eq!(Rounding::UpToGrid.apply(1), 64);
+        assert_eq!(Rounding::Off.apply(-7), -7);
+        assert_eq!(Rounding::new(0b0100_1000, 64).apply(80), 64);
+        assert_eq!(Rounding::new(0b0110_0000, 64).apply(80), 96);
+    }
+}
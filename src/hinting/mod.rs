@@ -0,0 +1,4 @@
+//! Hinting.
+
+pub mod instructions;
+pub mod machine;
@@ -1,19 +1,27 @@
 //! Builder and parser of TrueType fonts.
 
-#[macro_use(choices, dereference, flags, jump_take, raise, table)]
+#[macro_use(choices, dereference, flags, jump_take, jump_take_maybe, raise, table)]
 extern crate typeface;
 
+pub mod hinting;
+pub mod outline;
+pub mod rasterizer;
 pub mod tables;
 
+mod font;
 mod tag;
 
 pub use typeface::{q16, q32, tape, value, walue, Error, Result};
 
+pub use font::{Font, FontBuilder};
 pub use tag::Tag;
 
 /// A glyph identifier.
 pub type GlyphID = u16;
 
+/// The alignment of tables and glyphs in bytes.
+const ALIGNMENT: usize = 4;
+
 /// Check if a tag is recognized.
 #[inline]
 pub fn accept(tag: &Tag) -> bool {
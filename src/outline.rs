@@ -0,0 +1,408 @@
+//! Glyph outlines.
+
+use crate::tables::glyph_data::{Arguments, Description, GlyphData, Options, SimpleDescription};
+use crate::tables::maximum_profile::MaximumProfile;
+use crate::{GlyphID, Result};
+
+const MAX_DEPTH: usize = 16;
+
+/// An outline.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Outline(pub Vec<Contour>);
+
+/// A contour of an outline.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Contour(pub Vec<Point>);
+
+/// A point of a contour.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+    pub on_curve: bool,
+}
+
+/// A segment of a contour.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Segment {
+    /// A straight line from the first point to the second one.
+    Linear((f32, f32), (f32, f32)),
+    /// A quadratic Bézier curve from the first point to the third one with the
+    /// second one being the control point.
+    Quadratic((f32, f32), (f32, f32), (f32, f32)),
+}
+
+/// A resolver of glyphs into outlines.
+///
+/// Composite glyphs are flattened by recursively resolving their components,
+/// and the depth of recursion is limited by the maximum profile. Many fonts
+/// declare no depth, in which case a fixed limit of 16 is used instead.
+#[derive(Clone, Copy, Debug)]
+pub struct Resolver<'l> {
+    data: &'l GlyphData,
+    max_depth: usize,
+}
+
+/// A type that can draw outlines.
+pub trait Pen {
+    /// Start a new contour at a point.
+    fn move_to(&mut self, x: f32, y: f32);
+
+    /// Draw a straight line to a point.
+    fn line_to(&mut self, x: f32, y: f32);
+
+    /// Draw a quadratic Bézier curve to a point given a control point.
+    fn quad_to(&mut self, control_x: f32, control_y: f32, x: f32, y: f32);
+
+    /// Close the current contour.
+    fn close(&mut self);
+}
+
+dereference! { Outline::0 => [Contour] }
+dereference! { Contour::0 => [Point] }
+
+impl Outline {
+    /// Draw the outline using a pen.
+    pub fn draw<T: Pen>(&self, pen: &mut T) {
+        for contour in self.iter() {
+            contour.draw(pen);
+        }
+    }
+
+    /// Return the number of points.
+    #[inline]
+    pub fn point_count(&self) -> usize {
+        self.iter().map(|contour| contour.len()).sum()
+    }
+
+    /// Return the bounding box as `(min_x, min_y, max_x, max_y)` if any.
+    pub fn bounds(&self) -> Option<(f32, f32, f32, f32)> {
+        self.iter()
+            .flat_map(|contour| contour.iter())
+            .fold(None, |bounds, point| {
+                Some(match bounds {
+                    Some((min_x, min_y, max_x, max_y)) => (
+                        point.x.min(min_x),
+                        point.y.min(min_y),
+                        point.x.max(max_x),
+                        point.y.max(max_y),
+                    ),
+                    _ => (point.x, point.y, point.x, point.y),
+                })
+            })
+    }
+
+    /// Apply a function to each point.
+    pub fn transform<F>(&mut self, mut function: F)
+    where
+        F: FnMut(&mut Point),
+    {
+        for contour in self.0.iter_mut() {
+            for point in contour.0.iter_mut() {
+                function(point);
+            }
+        }
+    }
+}
+
+impl From<&SimpleDescription> for Outline {
+    fn from(description: &SimpleDescription) -> Self {
+        let mut contours = Vec::with_capacity(description.end_points.len());
+        let (mut x, mut y) = (0i32, 0i32);
+        let mut start = 0;
+        for &end in description.end_points.iter() {
+            let end = (end as usize + 1).min(description.flags.len());
+            let mut points = Vec::with_capacity(end.saturating_sub(start));
+            for i in start..end {
+                x += description.x[i] as i32;
+                y += description.y[i] as i32;
+                points.push(Point {
+                    x: x as f32,
+                    y: y as f32,
+                    on_curve: description.flags[i].is_on_curve(),
+                });
+            }
+            contours.push(Contour(points));
+            start = end;
+        }
+        Outline(contours)
+    }
+}
+
+impl<'l> Resolver<'l> {
+    /// Create an instance.
+    pub fn new(data: &'l GlyphData, profile: &MaximumProfile) -> Self {
+        let max_depth = match profile {
+            MaximumProfile::Version1(profile) if profile.max_component_depth > 0 => {
+                profile.max_component_depth as usize
+            }
+            _ => MAX_DEPTH,
+        };
+        Self { data, max_depth }
+    }
+
+    /// Resolve a glyph.
+    pub fn resolve(&self, glyph_id: GlyphID) -> Result<Outline> {
+        self.resolve_nested(glyph_id, &mut vec![])
+    }
+
+    fn resolve_nested(&self, glyph_id: GlyphID, stack: &mut Vec<GlyphID>) -> Result<Outline> {
+        macro_rules! reject(
+            () => (raise!("found a malformed component in the glyph with index {glyph_id}"));
+        );
+        let glyph = match self.data.get(glyph_id as usize) {
+            Some(Some(glyph)) => glyph,
+            Some(None) => return Ok(Default::default()),
+            _ => raise!("found no glyph with index {glyph_id}"),
+        };
+        let description = match glyph.description {
+            Description::Simple(ref description) => return Ok(Outline::from(description)),
+            Description::Composite(ref description) => description,
+        };
+        if stack.contains(&glyph_id) {
+            raise!("found a cyclic reference in the glyph with index {glyph_id}");
+        }
+        if stack.len() >= self.max_depth {
+            raise!(
+                "found the glyph with index {glyph_id} exceeding the maximum depth of {}",
+                self.max_depth,
+            );
+        }
+        stack.push(glyph_id);
+        let mut outline = Outline::default();
+        for component in description.components.iter() {
+            let mut other = self.resolve_nested(component.glyph_id, stack)?;
+            let (a, b, c, d) = match component.options {
+                Options::None => (1.0, 0.0, 0.0, 1.0),
+                Options::Scalar(scale) => (scale.into(), 0.0, 0.0, scale.into()),
+                Options::Vector(x, y) => (x.into(), 0.0, 0.0, y.into()),
+                Options::Matrix(a, b, c, d) => (a.into(), b.into(), c.into(), d.into()),
+            };
+            other.transform(|point| {
+                let (x, y) = (point.x, point.y);
+                point.x = a * x + c * y;
+                point.y = b * x + d * y;
+            });
+            let (x, y) = match component.arguments {
+                Arguments::Offsets(x, y) => {
+                    let (mut x, mut y) = (x as f32, y as f32);
+                    let flags = component.flags;
+                    if flags.is_offset_scaled() && !flags.is_offset_unscaled() {
+                        x *= f32::hypot(a, b);
+                        y *= f32::hypot(c, d);
+                    }
+                    if flags.should_round_xy_to_grid() {
+                        (x.round(), y.round())
+                    } else {
+                        (x, y)
+                    }
+                }
+                Arguments::Indices(i, j) => {
+                    let one = match outline
+                        .iter()
+                        .flat_map(|contour| contour.iter())
+                        .nth(i as _)
+                    {
+                        Some(point) => *point,
+                        _ => reject!(),
+                    };
+                    let other = match other.iter().flat_map(|contour| contour.iter()).nth(j as _) {
+                        Some(point) => *point,
+                        _ => reject!(),
+                    };
+                    (one.x - other.x, one.y - other.y)
+                }
+            };
+            other.transform(|point| {
+                point.x += x;
+                point.y += y;
+            });
+            outline.0.extend(other.0);
+        }
+        stack.pop();
+        Ok(outline)
+    }
+}
+
+impl Contour {
+    /// Draw the contour using a pen.
+    pub fn draw<T: Pen>(&self, pen: &mut T) {
+        let mut segments = self.segments().peekable();
+        match segments.peek() {
+            Some(Segment::Linear(start, _)) | Some(Segment::Quadratic(start, _, _)) => {
+                pen.move_to(start.0, start.1)
+            }
+            _ => return,
+        }
+        for segment in segments {
+            match segment {
+                Segment::Linear(_, end) => pen.line_to(end.0, end.1),
+                Segment::Quadratic(_, control, end) => {
+                    pen.quad_to(control.0, control.1, end.0, end.1)
+                }
+            }
+        }
+        pen.close();
+    }
+
+    /// Iterate over the segments.
+    ///
+    /// An on-curve point is implied in the middle of each pair of consecutive
+    /// off-curve points, and the contour is closed with a segment ending at
+    /// the starting point.
+    pub fn segments(&self) -> impl Iterator<Item = Segment> {
+        let points = &self.0;
+        let count = points.len();
+        let mut segments = Vec::with_capacity(count);
+        if count == 0 {
+            return segments.into_iter();
+        }
+        let (start, skip, take) = if points[0].on_curve {
+            (points[0], 1, count - 1)
+        } else if points[count - 1].on_curve {
+            (points[count - 1], 0, count - 1)
+        } else {
+            (middle(&points[count - 1], &points[0]), 0, count)
+        };
+        let mut current = start;
+        let mut control: Option<Point> = None;
+        for point in points
+            .iter()
+            .skip(skip)
+            .take(take)
+            .chain(std::iter::once(&start))
+        {
+            match (point.on_curve, control) {
+                (true, Some(other)) => {
+                    segments.push(Segment::Quadratic(
+                        (current.x, current.y),
+                        (other.x, other.y),
+                        (point.x, point.y),
+                    ));
+                    current = *point;
+                    control = None;
+                }
+                (true, None) => {
+                    segments.push(Segment::Linear((current.x, current.y), (point.x, point.y)));
+                    current = *point;
+                }
+                (false, Some(other)) => {
+                    let end = middle(&other, point);
+                    segments.push(Segment::Quadratic(
+                        (current.x, current.y),
+                        (other.x, other.y),
+                        (end.x, end.y),
+                    ));
+                    current = end;
+                    control = Some(*point);
+                }
+                (false, None) => control = Some(*point),
+            }
+        }
+        segments.into_iter()
+    }
+}
+
+#[inline]
+fn middle(one: &Point, other: &Point) -> Point {
+    Point {
+        x: (one.x + other.x) / 2.0,
+        y: (one.y + other.y) / 2.0,
+        on_curve: true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Contour, Point, Resolver, Segment};
+    use crate::tables::glyph_data::{
+        Arguments, Component, CompositeDescription, Description, Glyph, GlyphData,
+    };
+    use crate::tables::maximum_profile::{MaximumProfile, MaximumProfile1};
+
+    macro_rules! point(
+        ($x:expr, $y:expr, $on_curve:expr) => (
+            Point { x: $x as f32, y: $y as f32, on_curve: $on_curve }
+        );
+    );
+
+    #[test]
+    fn segments_linear() {
+        let contour = Contour(vec![
+            point!(0, 0, true),
+            point!(10, 0, true),
+            point!(10, 10, true),
+        ]);
+        assert_eq!(
+            contour.segments().collect::<Vec<_>>(),
+            vec![
+                Segment::Linear((0.0, 0.0), (10.0, 0.0)),
+                Segment::Linear((10.0, 0.0), (10.0, 10.0)),
+                Segment::Linear((10.0, 10.0), (0.0, 0.0)),
+            ],
+        );
+    }
+
+    #[test]
+    fn segments_off_curve() {
+        let contour = Contour(vec![
+            point!(0, 0, false),
+            point!(10, 0, false),
+            point!(10, 10, false),
+            point!(0, 10, false),
+        ]);
+        assert_eq!(
+            contour.segments().collect::<Vec<_>>(),
+            vec![
+                Segment::Quadratic((0.0, 5.0), (0.0, 0.0), (5.0, 0.0)),
+                Segment::Quadratic((5.0, 0.0), (10.0, 0.0), (10.0, 5.0)),
+                Segment::Quadratic((10.0, 5.0), (10.0, 10.0), (5.0, 10.0)),
+                Segment::Quadratic((5.0, 10.0), (0.0, 10.0), (0.0, 5.0)),
+            ],
+        );
+    }
+
+    #[test]
+    fn segments_starting_off_curve() {
+        let contour = Contour(vec![
+            point!(10, 0, false),
+            point!(10, 10, true),
+            point!(0, 0, true),
+        ]);
+        assert_eq!(
+            contour.segments().collect::<Vec<_>>(),
+            vec![
+                Segment::Quadratic((0.0, 0.0), (10.0, 0.0), (10.0, 10.0)),
+                Segment::Linear((10.0, 10.0), (0.0, 0.0)),
+            ],
+        );
+    }
+
+    #[test]
+    fn resolve_cyclic() {
+        let component = |glyph_id| Component {
+            glyph_id,
+            arguments: Arguments::Offsets(0, 0),
+            ..Default::default()
+        };
+        let glyph = |glyph_id| {
+            Some(Glyph {
+                contour_count: -1,
+                description: Description::Composite(CompositeDescription {
+                    components: vec![component(glyph_id)],
+                    instruction_size: 0,
+                    instructions: vec![],
+                }),
+                ..Default::default()
+            })
+        };
+        let data = GlyphData(vec![glyph(1), glyph(0), glyph(2)]);
+        let profile = MaximumProfile::Version1(MaximumProfile1 {
+            max_component_depth: 16,
+            ..Default::default()
+        });
+        let resolver = Resolver::new(&data, &profile);
+        assert!(resolver.resolve(0).is_err());
+        assert!(resolver.resolve(2).is_err());
+    }
+}
@@ -0,0 +1,220 @@
+//! Scan conversion of outlines.
+//!
+//! Outlines are converted into 8-bit coverage masks by accumulating the signed
+//! area covered by each line segment, with curves flattened beforehand. The
+//! absolute value of the accumulated area is clamped to one, which fills
+//! pixels according to the non-zero winding rule.
+
+use crate::outline::{Outline, Pen};
+use crate::Result;
+
+/// A coverage bitmap.
+///
+/// Rows are stored from top to bottom, and each pixel is a coverage value from
+/// 0 to 255.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Bitmap {
+    /// The width in pixels.
+    pub width: usize,
+    /// The height in pixels.
+    pub height: usize,
+    /// The horizontal position of the left edge relative to the origin.
+    pub left: i32,
+    /// The vertical position of the top edge relative to the baseline with the
+    /// vertical axis pointing up.
+    pub top: i32,
+    /// The coverage values.
+    pub data: Vec<u8>,
+}
+
+/// A rasterizer.
+///
+/// A point `(x, y)` given to the pen is mapped to `(x * scale + dx, dy - y *
+/// scale)` in the pixel space of the bitmap, where the vertical axis points
+/// down.
+#[derive(Clone, Debug)]
+pub struct Rasterizer {
+    width: usize,
+    height: usize,
+    scale: f32,
+    offset: (f32, f32),
+    accumulation: Vec<f32>,
+    start: (f32, f32),
+    current: (f32, f32),
+}
+
+impl Bitmap {
+    /// Render an outline given the number of units per em, the size in pixels
+    /// per em, and a subpixel offset.
+    pub fn render(
+        outline: &Outline,
+        units_per_em: u16,
+        size: f32,
+        (x, y): (f32, f32),
+    ) -> Result<Self> {
+        if units_per_em == 0 {
+            raise!("found a malformed number of units per em");
+        }
+        if !(size.is_finite() && size > 0.0) {
+            raise!("found a malformed size");
+        }
+        let scale = size / units_per_em as f32;
+        let (min_x, min_y, max_x, max_y) = match outline.bounds() {
+            Some(bounds) => bounds,
+            _ => return Ok(Default::default()),
+        };
+        let left = (min_x * scale + x).floor() as i32;
+        let right = (max_x * scale + x).ceil() as i32;
+        let bottom = (min_y * scale + y).floor() as i32;
+        let top = (max_y * scale + y).ceil() as i32;
+        let width = (right - left).max(1) as usize;
+        let height = (top - bottom).max(1) as usize;
+        let mut rasterizer =
+            Rasterizer::new(width, height, scale, (x - left as f32, top as f32 - y));
+        outline.draw(&mut rasterizer);
+        Ok(Self {
+            width,
+            height,
+            left,
+            top,
+            data: rasterizer.finish(),
+        })
+    }
+
+    /// Return the coverage of a pixel.
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> u8 {
+        self.data[y * self.width + x]
+    }
+
+    /// Encode the bitmap as a binary portable graymap.
+    pub fn to_pgm(&self) -> Vec<u8> {
+        let mut data = format!("P5\n{} {}\n255\n", self.width, self.height).into_bytes();
+        data.extend_from_slice(&self.data);
+        data
+    }
+}
+
+impl Rasterizer {
+    /// Create an instance.
+    pub fn new(width: usize, height: usize, scale: f32, offset: (f32, f32)) -> Self {
+        Self {
+            width,
+            height,
+            scale,
+            offset,
+            accumulation: vec![0.0; width * height + 2],
+            start: (0.0, 0.0),
+            current: (0.0, 0.0),
+        }
+    }
+
+    /// Finish the rasterization and return the coverage values.
+    pub fn finish(self) -> Vec<u8> {
+        let mut sum = 0.0;
+        self.accumulation
+            .iter()
+            .take(self.width * self.height)
+            .map(|value| {
+                sum += value;
+                (sum.abs().min(1.0) * 255.0).round() as u8
+            })
+            .collect()
+    }
+
+    fn transform(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            (x * self.scale + self.offset.0).clamp(0.0, self.width as f32),
+            (self.offset.1 - y * self.scale).clamp(0.0, self.height as f32),
+        )
+    }
+
+    fn line(&mut self, (x0, y0): (f32, f32), (x1, y1): (f32, f32)) {
+        if y0 == y1 {
+            return;
+        }
+        let (direction, (x0, y0), (x1, y1)) = if y0 < y1 {
+            (1.0, (x0, y0), (x1, y1))
+        } else {
+            (-1.0, (x1, y1), (x0, y0))
+        };
+        let slope = (x1 - x0) / (y1 - y0);
+        let mut x = x0;
+        for row in (y0 as usize)..(y1.ceil() as usize).min(self.height) {
+            let start = row * self.width;
+            let dy = ((row + 1) as f32).min(y1) - (row as f32).max(y0);
+            let next = x + slope * dy;
+            let delta = dy * direction;
+            let (lower, upper) = if x < next { (x, next) } else { (next, x) };
+            let floor = lower.floor();
+            let first = floor as usize;
+            let ceiling = upper.ceil();
+            let last = ceiling as usize;
+            if last <= first + 1 {
+                let middle = 0.5 * (x + next) - floor;
+                self.accumulation[start + first] += delta - delta * middle;
+                self.accumulation[start + first + 1] += delta * middle;
+            } else {
+                let inverse = (upper - lower).recip();
+                let lower_fraction = lower - floor;
+                let lower_area = 0.5 * inverse * (1.0 - lower_fraction) * (1.0 - lower_fraction);
+                let upper_fraction = upper - ceiling + 1.0;
+                let upper_area = 0.5 * inverse * upper_fraction * upper_fraction;
+                self.accumulation[start + first] += delta * lower_area;
+                if last == first + 2 {
+                    self.accumulation[start + first + 1] += delta * (1.0 - lower_area - upper_area);
+                } else {
+                    let area = inverse * (1.5 - lower_fraction);
+                    self.accumulation[start + first + 1] += delta * (area - lower_area);
+                    for column in (first + 2)..(last - 1) {
+                        self.accumulation[start + column] += delta * inverse;
+                    }
+                    let area = area + (last - first - 3) as f32 * inverse;
+                    self.accumulation[start + last - 1] += delta * (1.0 - area - upper_area);
+                }
+                self.accumulation[start + last] += delta * upper_area;
+            }
+            x = next;
+        }
+    }
+}
+
+impl Pen for Rasterizer {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.start = self.transform(x, y);
+        self.current = self.start;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let point = self.transform(x, y);
+        self.line(self.current, point);
+        self.current = point;
+    }
+
+    fn quad_to(&mut self, control_x: f32, control_y: f32, x: f32, y: f32) {
+        let (a, b, c) = (
+            self.current,
+            self.transform(control_x, control_y),
+            self.transform(x, y),
+        );
+        let deviation = (a.0 - 2.0 * b.0 + c.0).hypot(a.1 - 2.0 * b.1 + c.1);
+        let count = 1 + (3.0 * deviation).sqrt().floor() as usize;
+        let mut previous = a;
+        for i in 1..=count {
+            let t = i as f32 / count as f32;
+            let u = 1.0 - t;
+            let point = (
+                u * u * a.0 + 2.0 * u * t * b.0 + t * t * c.0,
+                u * u * a.1 + 2.0 * u * t * b.1 + t * t * c.1,
+            );
+            self.line(previous, point);
+            previous = point;
+        }
+        self.current = c;
+    }
+
+    fn close(&mut self) {
+        self.line(self.current, self.start);
+        self.current = self.start;
+    }
+}
@@ -3,8 +3,9 @@
 //! [1]: https://learn.microsoft.com/en-us/typography/opentype/spec/cmap
 
 use std::cmp::Eq;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::hash::Hash;
+use std::ops::RangeInclusive;
 
 use crate::{GlyphID, Result};
 
@@ -21,12 +22,20 @@ pub struct CharacterMapping {
 pub enum Encoding {
     /// Format 0.
     Format0(Encoding0),
+    /// Format 2.
+    Format2(Encoding2),
     /// Format 4.
     Format4(Encoding4),
     /// Format 6.
     Format6(Encoding6),
+    /// Format 8.
+    Format8(Encoding8),
+    /// Format 10.
+    Format10(Encoding10),
     /// Format 12.
     Format12(Encoding12),
+    /// Format 13.
+    Format13(Encoding13),
     /// Format 14.
     Format14(Encoding14),
     /// An unknown format.
@@ -34,6 +43,7 @@ pub enum Encoding {
 }
 
 table! {
+    @write
     /// The header of a character-to-glyph mapping.
     #[derive(Copy)]
     pub Header {
@@ -43,6 +53,7 @@ table! {
 }
 
 table! {
+    @write
     /// A record of a character-to-glyph mapping.
     #[derive(Copy)]
     pub Record {
@@ -53,6 +64,7 @@ table! {
 }
 
 table! {
+    @write
     /// A character-to-glyph encoding in format 0.
     pub Encoding0 {
         format   (u16) = { 0 }, // format
@@ -66,6 +78,29 @@ table! {
 }
 
 table! {
+    @write
+    /// A character-to-glyph encoding in format 2.
+    pub Encoding2 {
+        format   (u16) = { 2 }, // format
+        size     (u16), // length
+        language (u16), // language
+
+        sub_header_keys (Vec<u16>) |_, tape| { // subHeaderKeys
+            tape.take_given(256)
+        },
+
+        sub_headers (Vec<SubHeader>) |this, tape| { // subHeaders
+            tape.take_given(this.sub_header_count())
+        },
+
+        glyph_ids (Vec<GlyphID>) |this, tape| { // glyphIdArray
+            tape.take_given(this.glyph_id_count()?)
+        },
+    }
+}
+
+table! {
+    @write
     /// A character-to-glyph encoding in format 4.
     pub Encoding4 {
         format           (u16) = { 4 }, // format
@@ -101,6 +136,7 @@ table! {
 }
 
 table! {
+    @write
     /// A character-to-glyph encoding in format 6.
     pub Encoding6 {
         format      (u16) = { 6 }, // format
@@ -116,6 +152,45 @@ table! {
 }
 
 table! {
+    @write
+    /// A character-to-glyph encoding in format 8.
+    pub Encoding8 {
+        format      (u16) = { 8 }, // format
+        reserved    (u16) = { 0 }, // reserved
+        size        (u32), // length
+        language    (u32), // language
+
+        is32 (Vec<u8>) |_, tape| { // is32
+            tape.take_given(8192)
+        },
+
+        group_count (u32), // numGroups
+
+        groups (Vec<SequentialGroup>) |this, tape| { // groups
+            tape.take_given(this.group_count as usize)
+        },
+    }
+}
+
+table! {
+    @write
+    /// A character-to-glyph encoding in format 10.
+    pub Encoding10 {
+        format     (u16) = { 10 }, // format
+        reserved   (u16) = { 0 }, // reserved
+        size       (u32), // length
+        language   (u32), // language
+        start_code (u32), // startCharCode
+        code_count (u32), // numChars
+
+        glyph_ids (Vec<GlyphID>) |this, tape| { // glyphIdArray
+            tape.take_given(this.code_count as usize)
+        },
+    }
+}
+
+table! {
+    @write
     /// A character-to-glyph encoding in format 12.
     pub Encoding12 {
         format      (u16) = { 12 }, // format
@@ -131,19 +206,79 @@ table! {
 }
 
 table! {
+    @write
+    /// A character-to-glyph encoding in format 13.
+    pub Encoding13 {
+        format      (u16) = { 13 }, // format
+        reserved    (u16) = { 0 }, // reserved
+        size        (u32), // length
+        language    (u32), // language
+        group_count (u32), // numGroups
+
+        groups (Vec<ConstantGroup>) |this, tape| { // groups
+            tape.take_given(this.group_count as usize)
+        },
+    }
+}
+
+table! {
+    @position
     /// A character-to-glyph encoding in format 14.
     pub Encoding14 {
         format         (u16) = { 14 }, // format
         size           (u32), // length
         selector_count (u32), // numVarSelectorRecords
 
-        selectors (Vec<VariationSelector>) |this, tape| { // varSelector
+        selectors (Vec<VariationSelector>) |this, tape, _| { // varSelector
             tape.take_given(this.selector_count as usize)
         },
+
+        default_variations (Vec<Option<DefaultVariations>>) |this, tape, position| {
+            Ok(jump_take_maybe!(
+                @unwrap
+                tape,
+                position,
+                this.selector_count,
+                i => this.selectors[i].default_uvs_offset
+            ))
+        },
+
+        non_default_variations (Vec<Option<NonDefaultVariations>>) |this, tape, position| {
+            Ok(jump_take_maybe!(
+                @unwrap
+                tape,
+                position,
+                this.selector_count,
+                i => this.selectors[i].non_default_uvs_offset
+            ))
+        },
+    }
+}
+
+table! {
+    @write
+    /// A subheader of an encoding in format 2.
+    #[derive(Copy)]
+    pub SubHeader {
+        first_code      (u16), // firstCode
+        entry_count     (u16), // entryCount
+        id_delta        (i16), // idDelta
+        id_range_offset (u16), // idRangeOffset
+    }
+}
+
+table! {
+    @write
+    /// A constant mapping group.
+    pub ConstantGroup {
+        start_code (u32), // startCharCode
+        end_code   (u32), // endCharCode
+        glyph_id   (u32), // glyphID
     }
 }
 
 table! {
+    @write
     /// A sequential mapping group.
     pub SequentialGroup {
         start_code     (u32), // startCharCode
@@ -156,12 +291,7 @@ table! {
     /// A variation selector.
     pub VariationSelector {
         character (u32) |_, tape| { // varSelector
-            let buffer: [u8; 3] = tape.take()?;
-            Ok(u32::from_be(
-                u32::from(buffer[0]) << 8 |
-                u32::from(buffer[1]) << 16 |
-                u32::from(buffer[2]) << 24
-            ))
+            read_u24(tape)
         },
 
         default_uvs_offset     (u32), // defaultUVSOffset
@@ -169,6 +299,84 @@ table! {
     }
 }
 
+table! {
+    /// Default variations of a variation selector.
+    pub DefaultVariations { // DefaultUVS
+        range_count (u32), // numUnicodeValueRanges
+
+        ranges (Vec<VariationRange>) |this, tape| { // ranges
+            tape.take_given(this.range_count as usize)
+        },
+    }
+}
+
+table! {
+    /// Non-default variations of a variation selector.
+    pub NonDefaultVariations { // NonDefaultUVS
+        mapping_count (u32), // numUVSMappings
+
+        mappings (Vec<VariationMapping>) |this, tape| { // uvsMappings
+            tape.take_given(this.mapping_count as usize)
+        },
+    }
+}
+
+table! {
+    /// A range of characters with default variations.
+    #[derive(Copy)]
+    pub VariationRange { // UnicodeRange
+        start_code (u32) |_, tape| { // startUnicodeValue
+            read_u24(tape)
+        },
+
+        additional_count (u8), // additionalCount
+    }
+}
+
+table! {
+    /// A mapping of a character with a non-default variation.
+    #[derive(Copy)]
+    pub VariationMapping { // UVSMapping
+        code (u32) |_, tape| { // unicodeValue
+            read_u24(tape)
+        },
+
+        glyph_id (GlyphID), // glyphID
+    }
+}
+
+/// A glyph of a variation sequence.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Variant {
+    /// The glyph that the base character is mapped to by default.
+    Default(GlyphID),
+    /// A glyph specific to the variation sequence.
+    NonDefault(GlyphID),
+}
+
+/// A view of a character-to-glyph mapping.
+#[derive(Clone, Copy, Debug)]
+pub struct View<'l> {
+    /// The platform of the underlying encoding.
+    pub platform_id: u16,
+    /// The encoding of the underlying encoding.
+    pub encoding_id: u16,
+    /// The underlying encoding.
+    pub encoding: &'l Encoding,
+    symbol: bool,
+}
+
+/// A set of characters.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Coverage(Vec<(u32, u32)>);
+
+/// An index of characters by glyph.
+#[derive(Clone, Debug, Default)]
+pub struct ReverseMapping {
+    characters: BTreeMap<GlyphID, Vec<u32>>,
+    sequences: BTreeMap<GlyphID, Vec<(u32, u32)>>,
+}
+
 impl crate::value::Read for CharacterMapping {
     fn read<T: crate::tape::Read>(tape: &mut T) -> Result<Self> {
         let position = tape.position()?;
@@ -182,9 +390,13 @@ impl crate::value::Read for CharacterMapping {
             tape.jump(position + encoding.offset as u64)?;
             encodings.push(match tape.peek::<u16>()? {
                 0 => Encoding::Format0(tape.take()?),
+                2 => Encoding::Format2(tape.take()?),
                 4 => Encoding::Format4(tape.take()?),
                 6 => Encoding::Format6(tape.take()?),
+                8 => Encoding::Format8(tape.take()?),
+                10 => Encoding::Format10(tape.take()?),
                 12 => Encoding::Format12(tape.take()?),
+                13 => Encoding::Format13(tape.take()?),
                 14 => Encoding::Format14(tape.take()?),
                 format => Encoding::Unknown(format),
             });
@@ -197,6 +409,446 @@ impl crate::value::Read for CharacterMapping {
     }
 }
 
+impl crate::value::Write for CharacterMapping {
+    /// Write the table.
+    ///
+    /// The offsets of the records are recomputed, and identical encodings are
+    /// stored only once.
+    fn write<T: crate::tape::Write>(&self, tape: &mut T) -> Result<()> {
+        let (offsets, blocks) = self.layout()?;
+        tape.give(&Header {
+            version: 0,
+            table_count: self.records.len() as _,
+        })?;
+        for (record, offset) in self.records.iter().zip(offsets) {
+            tape.give(&Record {
+                platform_id: record.platform_id,
+                encoding_id: record.encoding_id,
+                offset,
+            })?;
+        }
+        for block in blocks {
+            tape.give_bytes(&block)?;
+        }
+        Ok(())
+    }
+}
+
+impl crate::value::Write for Encoding {
+    fn write<T: crate::tape::Write>(&self, tape: &mut T) -> Result<()> {
+        match self {
+            Encoding::Format0(value) => tape.give(value),
+            Encoding::Format2(value) => tape.give(value),
+            Encoding::Format4(value) => tape.give(value),
+            Encoding::Format6(value) => tape.give(value),
+            Encoding::Format8(value) => tape.give(value),
+            Encoding::Format10(value) => tape.give(value),
+            Encoding::Format12(value) => tape.give(value),
+            Encoding::Format13(value) => tape.give(value),
+            Encoding::Format14(value) => tape.give(value),
+            Encoding::Unknown(_) => {
+                raise!("found an unknown format of the character-to-glyph mapping")
+            }
+        }
+    }
+}
+
+impl crate::value::Write for Encoding14 {
+    /// Write the table.
+    ///
+    /// The size and the offsets of the variation tables are recomputed.
+    fn write<T: crate::tape::Write>(&self, tape: &mut T) -> Result<()> {
+        let (size, offsets) = self.layout();
+        tape.give(&self.format)?;
+        tape.give(&size)?;
+        tape.give(&(self.selectors.len() as u32))?;
+        for (selector, (default_uvs_offset, non_default_uvs_offset)) in
+            self.selectors.iter().zip(offsets)
+        {
+            write_u24(tape, selector.character)?;
+            tape.give(&default_uvs_offset)?;
+            tape.give(&non_default_uvs_offset)?;
+        }
+        for i in 0..self.selectors.len() {
+            if let Some(Some(variations)) = self.default_variations.get(i) {
+                tape.give(&(variations.ranges.len() as u32))?;
+                for range in variations.ranges.iter() {
+                    write_u24(tape, range.start_code)?;
+                    tape.give(&range.additional_count)?;
+                }
+            }
+            if let Some(Some(variations)) = self.non_default_variations.get(i) {
+                tape.give(&(variations.mappings.len() as u32))?;
+                for mapping in variations.mappings.iter() {
+                    write_u24(tape, mapping.code)?;
+                    tape.give(&mapping.glyph_id)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CharacterMapping {
+    /// Create an instance from a mapping of characters to glyphs.
+    ///
+    /// The basic multilingual plane is encoded in format 4, which is
+    /// complemented by format 12 if there are supplementary characters and by
+    /// format 14 if there are variation sequences. The sequences are keyed by
+    /// the base character and the variation selector, and the default glyph of
+    /// the base character is denoted by `None`. Characters mapped to glyph 0
+    /// are omitted.
+    pub fn from_mapping(
+        mapping: &BTreeMap<u32, GlyphID>,
+        variation_sequences: &BTreeMap<(u32, u32), Option<GlyphID>>,
+    ) -> Result<Self> {
+        let supplementary = mapping
+            .iter()
+            .any(|(&code, &glyph_id)| code > 0xFFFF && glyph_id != 0);
+        let mut entries = vec![];
+        let basic = Encoding::Format4(Encoding4::from_mapping(mapping)?);
+        entries.push(((0, 3), basic.clone()));
+        entries.push(((3, 1), basic));
+        if supplementary {
+            let full = Encoding::Format12(Encoding12::from_mapping(mapping));
+            entries.push(((0, 4), full.clone()));
+            entries.push(((3, 10), full));
+        }
+        if !variation_sequences.is_empty() {
+            let sequences = Encoding14::from_sequences(variation_sequences);
+            entries.push(((0, 5), Encoding::Format14(sequences)));
+        }
+        entries.sort_by_key(|&(key, _)| key);
+        let (records, encodings): (Vec<_>, Vec<_>) = entries
+            .into_iter()
+            .map(|((platform_id, encoding_id), encoding)| {
+                let record = Record {
+                    platform_id,
+                    encoding_id,
+                    offset: 0,
+                };
+                (record, encoding)
+            })
+            .unzip();
+        let mut table = CharacterMapping {
+            header: Header {
+                version: 0,
+                table_count: records.len() as _,
+            },
+            records,
+            encodings,
+        };
+        let (offsets, _) = table.layout()?;
+        for (record, offset) in table.records.iter_mut().zip(offsets) {
+            record.offset = offset;
+        }
+        Ok(table)
+    }
+
+    /// Return the glyph of a character.
+    ///
+    /// The character is looked up in the view returned by `view`.
+    #[inline]
+    pub fn lookup(&self, code: u32) -> Option<GlyphID> {
+        self.view()?.lookup(code)
+    }
+
+    /// Return the characters covered by the Unicode encodings.
+    ///
+    /// Characters mapped to glyph 0 are not considered covered, and last-resort
+    /// encodings in format 13 are ignored.
+    pub fn coverage(&self) -> Coverage {
+        let mut ranges = self
+            .records
+            .iter()
+            .zip(self.encodings.iter())
+            .filter(|(record, encoding)| {
+                record.is_unicode() && !matches!(encoding, Encoding::Format13(_))
+            })
+            .flat_map(|(_, encoding)| encoding.ranges())
+            .collect::<Vec<_>>();
+        ranges.sort_unstable();
+        let mut merged: Vec<(u32, u32)> = vec![];
+        for (start, end) in ranges {
+            match merged.last_mut() {
+                Some(range) if start <= range.1.saturating_add(1) => range.1 = range.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+        Coverage(merged)
+    }
+
+    /// Build an index of characters by glyph.
+    ///
+    /// The characters are gathered from all Unicode encodings except last-resort
+    /// ones in format 13, which map whole ranges to one glyph. If requested,
+    /// the variation sequences of encodings in format 14 are gathered too, in
+    /// which case the default variations are attributed to the glyph that the
+    /// base character is mapped to.
+    pub fn reverse(&self, sequences: bool) -> ReverseMapping {
+        let mut index = ReverseMapping::default();
+        let view = self.view();
+        for (record, encoding) in self.records.iter().zip(self.encodings.iter()) {
+            match encoding {
+                Encoding::Format14(encoding) if sequences => {
+                    for (i, selector) in encoding.selectors.iter().enumerate() {
+                        let selector = selector.character;
+                        if let Some(Some(variations)) = encoding.default_variations.get(i) {
+                            for range in variations.ranges.iter() {
+                                let end_code = range.start_code + range.additional_count as u32;
+                                for code in range.start_code..=end_code {
+                                    if let Some(glyph_id) = view.and_then(|view| view.lookup(code))
+                                    {
+                                        index.add_sequence(glyph_id, (code, selector));
+                                    }
+                                }
+                            }
+                        }
+                        if let Some(Some(variations)) = encoding.non_default_variations.get(i) {
+                            for mapping in variations.mappings.iter() {
+                                index.add_sequence(mapping.glyph_id, (mapping.code, selector));
+                            }
+                        }
+                    }
+                }
+                Encoding::Format13(_) | Encoding::Format14(_) => {}
+                _ if record.is_unicode() => {
+                    for (code, glyph_id) in encoding.iter() {
+                        index.add_character(glyph_id, code);
+                    }
+                }
+                _ => {}
+            }
+        }
+        for values in index.characters.values_mut() {
+            values.sort_unstable();
+            values.dedup();
+        }
+        for values in index.sequences.values_mut() {
+            values.sort_unstable();
+            values.dedup();
+        }
+        index
+    }
+
+    /// Return the best view of the mapping.
+    ///
+    /// The Unicode encodings are preferred in the order of 3/10, 0/4, 3/1, 0/3,
+    /// 0/2, 0/1, and 0/0, where the first number is the platform and the second
+    /// is the encoding. If there are none, the Windows Symbol encoding 3/0 is
+    /// used, in which case characters are remapped from the range starting at
+    /// U+F000.
+    pub fn view(&self) -> Option<View<'_>> {
+        const PRIORITIES: [(u16, u16); 8] = [
+            (3, 10),
+            (0, 4),
+            (3, 1),
+            (0, 3),
+            (0, 2),
+            (0, 1),
+            (0, 0),
+            (3, 0),
+        ];
+        PRIORITIES.iter().find_map(|&(platform_id, encoding_id)| {
+            self.records
+                .iter()
+                .zip(self.encodings.iter())
+                .find(|(record, encoding)| {
+                    record.platform_id == platform_id
+                        && record.encoding_id == encoding_id
+                        && !matches!(encoding, Encoding::Format14(_) | Encoding::Unknown(_))
+                })
+                .map(|(record, encoding)| View {
+                    platform_id,
+                    encoding_id,
+                    encoding,
+                    symbol: record.platform_id == 3 && record.encoding_id == 0,
+                })
+        })
+    }
+
+    fn layout(&self) -> Result<(Vec<u32>, Vec<Vec<u8>>)> {
+        use crate::tape::Write;
+
+        let mut offset = 4 + 8 * self.records.len() as u32;
+        let mut offsets = Vec::with_capacity(self.encodings.len());
+        let mut blocks: Vec<(u32, Vec<u8>)> = vec![];
+        for encoding in self.encodings.iter() {
+            let mut data = vec![];
+            data.give(encoding)?;
+            match blocks.iter().find(|(_, other)| other == &data) {
+                Some(&(other, _)) => offsets.push(other),
+                _ => {
+                    offsets.push(offset);
+                    offset += data.len() as u32;
+                    blocks.push((offsets[offsets.len() - 1], data));
+                }
+            }
+        }
+        Ok((offsets, blocks.into_iter().map(|(_, data)| data).collect()))
+    }
+}
+
+impl Coverage {
+    /// Return the ranges of characters in ascending order.
+    ///
+    /// The ranges are inclusive, disjoint, and not adjacent.
+    #[inline]
+    pub fn ranges(&self) -> &[(u32, u32)] {
+        &self.0
+    }
+
+    /// Check if a character is covered.
+    pub fn contains(&self, code: u32) -> bool {
+        let i = self.0.partition_point(|&(_, end)| end < code);
+        matches!(self.0.get(i), Some(&(start, _)) if start <= code)
+    }
+
+    /// Check if all characters of a text are covered.
+    pub fn supports(&self, text: &str) -> bool {
+        text.chars()
+            .all(|character| self.contains(character as u32))
+    }
+}
+
+impl Record {
+    fn is_unicode(&self) -> bool {
+        matches!(
+            (self.platform_id, self.encoding_id),
+            (0, 0..=4) | (3, 1) | (3, 10)
+        )
+    }
+}
+
+impl ReverseMapping {
+    /// Return the characters of a glyph in ascending order.
+    #[inline]
+    pub fn characters(&self, glyph_id: GlyphID) -> &[u32] {
+        self.characters.get(&glyph_id).map_or(&[], |values| values)
+    }
+
+    /// Return the variation sequences of a glyph in ascending order.
+    ///
+    /// A sequence is a pair of a base character and a variation selector.
+    #[inline]
+    pub fn sequences(&self, glyph_id: GlyphID) -> &[(u32, u32)] {
+        self.sequences.get(&glyph_id).map_or(&[], |values| values)
+    }
+
+    /// Iterate over glyphs and their characters in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = (GlyphID, &[u32])> {
+        self.characters
+            .iter()
+            .map(|(&glyph_id, values)| (glyph_id, &values[..]))
+    }
+
+    fn add_character(&mut self, glyph_id: GlyphID, code: u32) {
+        if glyph_id != 0 {
+            self.characters.entry(glyph_id).or_default().push(code);
+        }
+    }
+
+    fn add_sequence(&mut self, glyph_id: GlyphID, sequence: (u32, u32)) {
+        if glyph_id != 0 {
+            self.sequences.entry(glyph_id).or_default().push(sequence);
+        }
+    }
+}
+
+impl<'l> View<'l> {
+    /// Check if the view remaps the Windows Symbol encoding.
+    #[inline]
+    pub fn is_symbol(&self) -> bool {
+        self.symbol
+    }
+
+    /// Return the glyph of a character.
+    ///
+    /// In the symbol mode, a character below U+0100 that is not mapped itself
+    /// is looked up at U+F000 plus the character.
+    pub fn lookup(&self, code: u32) -> Option<GlyphID> {
+        match self.encoding.lookup(code) {
+            None if self.symbol && code < 0x100 => self.encoding.lookup(0xF000 + code),
+            glyph_id => glyph_id,
+        }
+    }
+
+    /// Iterate over characters and glyphs.
+    ///
+    /// In the symbol mode, the characters in the range from U+F000 to U+F0FF
+    /// are moved to the range from U+0000 to U+00FF.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, GlyphID)> + 'l {
+        let symbol = self.symbol;
+        self.encoding.iter().map(move |(code, glyph_id)| {
+            if symbol && (0xF000..0xF100).contains(&code) {
+                (code - 0xF000, glyph_id)
+            } else {
+                (code, glyph_id)
+            }
+        })
+    }
+}
+
+impl Encoding {
+    /// Return the glyph of a character.
+    ///
+    /// Characters that are not mapped or mapped to glyph 0, which is the
+    /// missing glyph, are reported as `None` by all formats.
+    pub fn lookup(&self, code: u32) -> Option<GlyphID> {
+        match self {
+            Encoding::Format0(encoding) => encoding.lookup(code),
+            Encoding::Format2(encoding) => encoding.lookup(code),
+            Encoding::Format4(encoding) => encoding.lookup(code),
+            Encoding::Format6(encoding) => encoding.lookup(code),
+            Encoding::Format8(encoding) => encoding.lookup(code),
+            Encoding::Format10(encoding) => encoding.lookup(code),
+            Encoding::Format12(encoding) => encoding.lookup(code),
+            Encoding::Format13(encoding) => encoding.lookup(code),
+            Encoding::Format14(_) | Encoding::Unknown(_) => None,
+        }
+    }
+
+    /// Iterate over characters and glyphs.
+    pub fn iter(&self) -> Box<dyn Iterator<Item = (u32, GlyphID)> + '_> {
+        match self {
+            Encoding::Format0(encoding) => Box::new(encoding.iter()),
+            Encoding::Format2(encoding) => Box::new(encoding.iter()),
+            Encoding::Format4(encoding) => Box::new(encoding.iter()),
+            Encoding::Format6(encoding) => Box::new(encoding.iter()),
+            Encoding::Format8(encoding) => Box::new(encoding.iter()),
+            Encoding::Format10(encoding) => Box::new(encoding.iter()),
+            Encoding::Format12(encoding) => Box::new(encoding.iter()),
+            Encoding::Format13(encoding) => Box::new(encoding.iter()),
+            Encoding::Format14(_) | Encoding::Unknown(_) => Box::new(std::iter::empty()),
+        }
+    }
+
+    fn ranges(&self) -> Vec<(u32, u32)> {
+        match self {
+            Encoding::Format8(encoding) => SequentialGroup::ranges(&encoding.groups),
+            Encoding::Format12(encoding) => SequentialGroup::ranges(&encoding.groups),
+            Encoding::Format13(encoding) => encoding
+                .groups
+                .iter()
+                .filter(|group| group.glyph_id != 0 && group.start_code <= group.end_code)
+                .map(|group| (group.start_code, group.end_code))
+                .collect(),
+            // The other formats store a glyph per character, so they are
+            // bounded by the size of the table.
+            _ => {
+                let mut ranges: Vec<(u32, u32)> = vec![];
+                for (code, _) in self.iter().filter(|&(_, glyph_id)| glyph_id != 0) {
+                    match ranges.last_mut() {
+                        Some(range) if range.1.checked_add(1) == Some(code) => range.1 = code,
+                        _ => ranges.push((code, code)),
+                    }
+                }
+                ranges
+            }
+        }
+    }
+}
+
 impl Encoding0 {
     /// Return the characters.
     #[inline]
@@ -206,11 +858,130 @@ impl Encoding0 {
 
     /// Return the mapping.
     pub fn mapping<T: From<u8> + Eq + Hash>(&self) -> HashMap<T, GlyphID> {
-        let mut result = HashMap::new();
-        for (i, glyph_id) in self.glyph_ids.iter().enumerate() {
-            result.insert(T::from(i as u8), *glyph_id as GlyphID);
+        self.iter()
+            .map(|(code, glyph_id)| (T::from(code as u8), glyph_id))
+            .collect()
+    }
+
+    /// Return the glyph of a character.
+    #[inline]
+    pub fn lookup(&self, code: u32) -> Option<GlyphID> {
+        self.glyph_ids
+            .get(code as usize)
+            .map(|&glyph_id| glyph_id as GlyphID)
+            .filter(|&glyph_id| glyph_id != 0)
+    }
+
+    /// Iterate over characters and glyphs.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, GlyphID)> + '_ {
+        self.glyph_ids
+            .iter()
+            .enumerate()
+            .map(|(i, &glyph_id)| (i as u32, glyph_id as GlyphID))
+    }
+}
+
+impl Encoding2 {
+    /// Return the characters.
+    pub fn characters<T: From<u16>>(&self) -> Vec<(T, T)> {
+        let mut ranges: Vec<(u16, u16)> = vec![];
+        for (_, range) in self.ranges() {
+            match ranges.last_mut() {
+                Some(last) if last.1 as u32 + 1 == *range.start() as u32 => last.1 = *range.end(),
+                _ => ranges.push((*range.start(), *range.end())),
+            }
+        }
+        ranges
+            .into_iter()
+            .map(|(start, end)| (T::from(start), T::from(end)))
+            .collect()
+    }
+
+    /// Return the mapping.
+    pub fn mapping<T: From<u16> + Eq + Hash>(&self) -> HashMap<T, GlyphID> {
+        self.iter()
+            .map(|(code, glyph_id)| (T::from(code as u16), glyph_id))
+            .collect()
+    }
+
+    /// Return the glyph of a character.
+    pub fn lookup(&self, code: u32) -> Option<GlyphID> {
+        let code = u16::try_from(code).ok()?;
+        let i = if code < 0x100 && self.sub_header_keys[code as usize] == 0 {
+            0
+        } else {
+            match self.sub_header_keys[(code >> 8) as usize] as usize / 8 {
+                0 => return None,
+                i => i,
+            }
+        };
+        self.glyph_id(i, code).filter(|&glyph_id| glyph_id != 0)
+    }
+
+    /// Iterate over characters and glyphs.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, GlyphID)> + '_ {
+        self.ranges().flat_map(move |(i, range)| {
+            range.filter_map(move |code| {
+                self.glyph_id(i, code)
+                    .map(|glyph_id| (code as u32, glyph_id))
+            })
+        })
+    }
+
+    fn glyph_id(&self, i: usize, code: u16) -> Option<GlyphID> {
+        let header = self.sub_headers.get(i)?;
+        let offset = (code & 0xFF).checked_sub(header.first_code)? as usize;
+        if offset >= header.entry_count as usize {
+            return None;
+        }
+        // The offset is relative to the idRangeOffset field of the subheader.
+        let index = (header.id_range_offset as usize + 2 * offset)
+            .checked_sub(8 * (self.sub_headers.len() - i) - 6)?
+            / 2;
+        match *self.glyph_ids.get(index)? {
+            0 => Some(0),
+            glyph_id => Some(glyph_id.wrapping_add(header.id_delta as u16)),
         }
-        result
+    }
+
+    fn ranges(&self) -> impl Iterator<Item = (usize, RangeInclusive<u16>)> + '_ {
+        (0..256u16).filter_map(move |high| {
+            let i = self.sub_header_keys[high as usize] as usize / 8;
+            let header = self.sub_headers.get(i)?;
+            if header.entry_count == 0 {
+                return None;
+            }
+            let start = header.first_code as u32;
+            let end = start + header.entry_count as u32 - 1;
+            if i == 0 {
+                if start <= high as u32 && high as u32 <= end {
+                    Some((i, high..=high))
+                } else {
+                    None
+                }
+            } else if end <= 0xFF {
+                let base = (high as u32) << 8;
+                Some((i, ((base + start) as u16)..=((base + end) as u16)))
+            } else {
+                None
+            }
+        })
+    }
+
+    fn glyph_id_count(&self) -> Result<usize> {
+        let size = 2 * 3 + 2 * 256 + 8 * self.sub_headers.len();
+        match (self.size as usize).checked_sub(size) {
+            Some(value) => Ok(value / 2),
+            _ => raise!("found a malformed character-to-glyph mapping"),
+        }
+    }
+
+    fn sub_header_count(&self) -> usize {
+        self.sub_header_keys
+            .iter()
+            .map(|&key| key as usize / 8 + 1)
+            .max()
+            .unwrap_or(0)
     }
 }
 
@@ -228,29 +999,149 @@ impl Encoding4 {
 
     /// Return the mapping.
     pub fn mapping<T: From<u16> + Eq + Hash>(&self) -> HashMap<T, GlyphID> {
-        use std::num::Wrapping;
+        self.iter()
+            .map(|(code, glyph_id)| (T::from(code as u16), glyph_id))
+            .collect()
+    }
 
-        let segment_count = self.segment_count();
-        if segment_count == 0 {
-            return Default::default();
+    /// Return the glyph of a character.
+    pub fn lookup(&self, code: u32) -> Option<GlyphID> {
+        let code = u16::try_from(code).ok()?;
+        let segment_count = self.segment_count().saturating_sub(1);
+        let i = self.end_codes[..segment_count].partition_point(|&end_code| end_code < code);
+        if i < segment_count && self.start_codes[i] <= code {
+            Some(self.glyph_id(i, code)).filter(|&glyph_id| glyph_id != 0)
+        } else {
+            None
         }
-        let mut result = HashMap::new();
-        for i in 0..(segment_count - 1) {
-            let start_code = self.start_codes[i];
-            let id_delta = self.id_deltas[i];
-            let id_range_offset = self.id_range_offsets[i];
-            for j in start_code..(self.end_codes[i] + 1) {
-                let id = if id_range_offset > 0 {
-                    let offset =
-                        (id_range_offset / 2 + (j - start_code)) - (segment_count - i) as u16;
-                    self.glyph_ids[offset as usize]
+    }
+
+    /// Iterate over characters and glyphs.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, GlyphID)> + '_ {
+        let segment_count = self.segment_count().saturating_sub(1);
+        (0..segment_count).flat_map(move |i| {
+            let range = (self.start_codes[i] as u32)..=(self.end_codes[i] as u32);
+            range.map(move |code| (code, self.glyph_id(i, code as u16)))
+        })
+    }
+
+    fn from_mapping(mapping: &BTreeMap<u32, GlyphID>) -> Result<Self> {
+        // A segment spans a run of consecutive characters and maps them either
+        // by adding a delta to the character or via the array of glyphs.
+        let mut runs: Vec<Vec<(u16, GlyphID)>> = vec![];
+        for (&code, &glyph_id) in mapping.range(..0xFFFF) {
+            if glyph_id == 0 {
+                continue;
+            }
+            let code = code as u16;
+            match runs.last_mut() {
+                Some(run) if run[run.len() - 1].0 + 1 == code => run.push((code, glyph_id)),
+                _ => runs.push(vec![(code, glyph_id)]),
+            }
+        }
+        let mut segments: Vec<(u16, u16, Option<i16>)> = vec![];
+        for run in runs.iter() {
+            let mut parts: Vec<(usize, usize)> = vec![];
+            for (i, &(code, glyph_id)) in run.iter().enumerate() {
+                let delta = glyph_id.wrapping_sub(code);
+                match parts.last_mut() {
+                    Some((start, end)) if run[*start].1.wrapping_sub(run[*start].0) == delta => {
+                        *end = i
+                    }
+                    _ => parts.push((i, i)),
+                }
+            }
+            let count = parts.len();
+            let mut array: Option<(usize, usize)> = None;
+            for (k, &(start, end)) in parts.iter().enumerate() {
+                // Splitting a part off costs one segment at the edges of the
+                // run and two segments in the middle.
+                let threshold = if k == 0 || k + 1 == count { 4 } else { 8 };
+                if count == 1 || end - start + 1 > threshold {
+                    if let Some((start, end)) = array.take() {
+                        segments.push((run[start].0, run[end].0, None));
+                    }
+                    let delta = run[start].1.wrapping_sub(run[start].0) as i16;
+                    segments.push((run[start].0, run[end].0, Some(delta)));
                 } else {
-                    (Wrapping(id_delta) + Wrapping(j as i16)).0 as u16
-                };
-                result.insert(T::from(j), id);
+                    array = match array {
+                        Some((first, _)) => Some((first, end)),
+                        _ => Some((start, end)),
+                    };
+                }
+            }
+            if let Some((start, end)) = array {
+                segments.push((run[start].0, run[end].0, None));
             }
         }
-        result
+        segments.push((0xFFFF, 0xFFFF, Some(1)));
+
+        let segment_count = segments.len();
+        let mut end_codes = Vec::with_capacity(segment_count);
+        let mut start_codes = Vec::with_capacity(segment_count);
+        let mut id_deltas = Vec::with_capacity(segment_count);
+        let mut id_range_offsets = Vec::with_capacity(segment_count);
+        let mut glyph_ids = vec![];
+        for (i, &(start_code, end_code, delta)) in segments.iter().enumerate() {
+            start_codes.push(start_code);
+            end_codes.push(end_code);
+            match delta {
+                Some(delta) => {
+                    id_deltas.push(delta);
+                    id_range_offsets.push(0);
+                }
+                _ => {
+                    let offset = 2 * (segment_count - i + glyph_ids.len());
+                    if offset > u16::MAX as usize {
+                        raise!("found a character-to-glyph mapping too large for format 4");
+                    }
+                    id_deltas.push(0);
+                    id_range_offsets.push(offset as u16);
+                    for code in start_code..=end_code {
+                        glyph_ids.push(mapping[&(code as u32)]);
+                    }
+                }
+            }
+        }
+        let size = 2 * (8 + 4 * segment_count + glyph_ids.len());
+        if size > u16::MAX as usize {
+            raise!("found a character-to-glyph mapping too large for format 4");
+        }
+        let entry_selector = segment_count.ilog2() as u16;
+        let search_range = 2 * (1 << entry_selector);
+        Ok(Self {
+            format: 4,
+            size: size as _,
+            language: 0,
+            segment_count_x2: 2 * segment_count as u16,
+            search_range,
+            entry_selector,
+            range_shift: 2 * segment_count as u16 - search_range,
+            end_codes,
+            reserved: 0,
+            start_codes,
+            id_deltas,
+            id_range_offsets,
+            glyph_ids,
+        })
+    }
+
+    fn glyph_id(&self, i: usize, code: u16) -> GlyphID {
+        use std::num::Wrapping;
+
+        let id_delta = Wrapping(self.id_deltas[i] as u16);
+        let id_range_offset = self.id_range_offsets[i] as usize;
+        if id_range_offset > 0 {
+            // The offset is relative to the idRangeOffset field of the segment.
+            let index = (id_range_offset / 2 + (code - self.start_codes[i]) as usize)
+                .wrapping_sub(self.segment_count() - i);
+            match self.glyph_ids.get(index) {
+                Some(&0) | None => 0,
+                Some(&glyph_id) => (Wrapping(glyph_id) + id_delta).0,
+            }
+        } else {
+            (Wrapping(code) + id_delta).0
+        }
     }
 
     fn glyph_id_count(&self) -> Result<usize> {
@@ -301,34 +1192,375 @@ impl Encoding6 {
 
     /// Return the mapping.
     pub fn mapping<T: From<u16> + Eq + Hash>(&self) -> HashMap<T, GlyphID> {
-        let mut result = HashMap::new();
-        for (i, glyph_id) in self.glyph_ids.iter().enumerate() {
-            result.insert(T::from(self.first_code + i as u16), *glyph_id);
+        self.iter()
+            .map(|(code, glyph_id)| (T::from(code as u16), glyph_id))
+            .collect()
+    }
+
+    /// Return the glyph of a character.
+    #[inline]
+    pub fn lookup(&self, code: u32) -> Option<GlyphID> {
+        let index = code.checked_sub(self.first_code as u32)?;
+        self.glyph_ids
+            .get(index as usize)
+            .copied()
+            .filter(|&glyph_id| glyph_id != 0)
+    }
+
+    /// Iterate over characters and glyphs.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, GlyphID)> + '_ {
+        let first_code = self.first_code as u32;
+        self.glyph_ids
+            .iter()
+            .enumerate()
+            .map(move |(i, &glyph_id)| (first_code + i as u32, glyph_id))
+    }
+}
+
+impl Encoding8 {
+    /// Return the characters.
+    pub fn characters<T: From<u32>>(&self) -> Vec<(T, T)> {
+        self.groups
+            .iter()
+            .map(|group| (T::from(group.start_code), T::from(group.end_code)))
+            .collect()
+    }
+
+    /// Return the mapping.
+    pub fn mapping<T: From<u32> + Eq + Hash>(&self) -> HashMap<T, GlyphID> {
+        self.iter()
+            .map(|(code, glyph_id)| (T::from(code), glyph_id))
+            .collect()
+    }
+
+    /// Return the glyph of a character.
+    #[inline]
+    pub fn lookup(&self, code: u32) -> Option<GlyphID> {
+        SequentialGroup::lookup(&self.groups, code)
+    }
+
+    /// Iterate over characters and glyphs.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (u32, GlyphID)> + '_ {
+        SequentialGroup::iter(&self.groups)
+    }
+}
+
+impl Encoding10 {
+    /// Return the characters.
+    ///
+    /// Glyphs that would map to characters beyond `u32::MAX` are ignored.
+    pub fn characters<T: From<u32>>(&self) -> Vec<(T, T)> {
+        if self.code_count == 0 {
+            return Default::default();
         }
-        result
+        vec![(
+            T::from(self.start_code),
+            T::from(self.start_code.saturating_add(self.code_count - 1)),
+        )]
+    }
+
+    /// Return the mapping.
+    pub fn mapping<T: From<u32> + Eq + Hash>(&self) -> HashMap<T, GlyphID> {
+        self.iter()
+            .map(|(code, glyph_id)| (T::from(code), glyph_id))
+            .collect()
+    }
+
+    /// Return the glyph of a character.
+    #[inline]
+    pub fn lookup(&self, code: u32) -> Option<GlyphID> {
+        let index = code.checked_sub(self.start_code)?;
+        self.glyph_ids
+            .get(index as usize)
+            .copied()
+            .filter(|&glyph_id| glyph_id != 0)
+    }
+
+    /// Iterate over characters and glyphs.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, GlyphID)> + '_ {
+        (self.start_code..=u32::MAX).zip(self.glyph_ids.iter().copied())
     }
 }
 
 impl Encoding12 {
     /// Return the characters.
+    pub fn characters<T: From<u32>>(&self) -> Vec<(T, T)> {
+        self.groups
+            .iter()
+            .map(|group| (T::from(group.start_code), T::from(group.end_code)))
+            .collect()
+    }
+
+    /// Return the mapping.
+    pub fn mapping<T: From<u32> + Eq + Hash>(&self) -> HashMap<T, GlyphID> {
+        self.iter()
+            .map(|(code, glyph_id)| (T::from(code), glyph_id))
+            .collect()
+    }
+
+    /// Return the glyph of a character.
     #[inline]
+    pub fn lookup(&self, code: u32) -> Option<GlyphID> {
+        SequentialGroup::lookup(&self.groups, code)
+    }
+
+    /// Iterate over characters and glyphs.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (u32, GlyphID)> + '_ {
+        SequentialGroup::iter(&self.groups)
+    }
+}
+
+impl Encoding12 {
+    fn from_mapping(mapping: &BTreeMap<u32, GlyphID>) -> Self {
+        let mut groups: Vec<SequentialGroup> = vec![];
+        for (&code, &glyph_id) in mapping.iter() {
+            if glyph_id == 0 {
+                continue;
+            }
+            match groups.last_mut() {
+                Some(group)
+                    if group.end_code + 1 == code
+                        && group.start_glyph_id + (code - group.start_code) == glyph_id as u32 =>
+                {
+                    group.end_code = code
+                }
+                _ => groups.push(SequentialGroup {
+                    start_code: code,
+                    end_code: code,
+                    start_glyph_id: glyph_id as u32,
+                }),
+            }
+        }
+        Self {
+            format: 12,
+            reserved: 0,
+            size: 16 + 12 * groups.len() as u32,
+            language: 0,
+            group_count: groups.len() as _,
+            groups,
+        }
+    }
+}
+
+impl Encoding13 {
+    /// Return the characters.
     pub fn characters<T: From<u32>>(&self) -> Vec<(T, T)> {
-        Default::default()
+        self.groups
+            .iter()
+            .map(|group| (T::from(group.start_code), T::from(group.end_code)))
+            .collect()
     }
 
     /// Return the mapping.
     pub fn mapping<T: From<u32> + Eq + Hash>(&self) -> HashMap<T, GlyphID> {
-        let mut result = HashMap::new();
-        for group in &self.groups {
-            for i in 0..(group.end_code - group.start_code + 1) {
-                result.insert(
-                    T::from(group.start_code + i),
-                    group.start_glyph_id as u16 + i as u16,
-                );
+        self.iter()
+            .map(|(code, glyph_id)| (T::from(code), glyph_id))
+            .collect()
+    }
+
+    /// Return the glyph of a character.
+    pub fn lookup(&self, code: u32) -> Option<GlyphID> {
+        let i = self.groups.partition_point(|group| group.end_code < code);
+        match self.groups.get(i) {
+            Some(group) if group.start_code <= code && group.glyph_id != 0 => {
+                Some(group.glyph_id as GlyphID)
             }
+            _ => None,
         }
-        result
     }
+
+    /// Iterate over characters and glyphs.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, GlyphID)> + '_ {
+        self.groups.iter().flat_map(|group| {
+            (group.start_code..=group.end_code).map(|code| (code, group.glyph_id as GlyphID))
+        })
+    }
+}
+
+impl Encoding14 {
+    /// Return the glyph of a variation sequence.
+    ///
+    /// The glyph of a default variation is looked up in the fallback encoding,
+    /// which is normally the main Unicode encoding of the font.
+    pub fn glyph_for(&self, base: char, selector: char, fallback: &Encoding) -> Option<Variant> {
+        let (base, selector) = (base as u32, selector as u32);
+        let i = self
+            .selectors
+            .binary_search_by_key(&selector, |record| record.character)
+            .ok()?;
+        if let Some(Some(variations)) = self.default_variations.get(i) {
+            let j = variations
+                .ranges
+                .partition_point(|range| range.start_code + (range.additional_count as u32) < base);
+            if matches!(variations.ranges.get(j), Some(range) if range.start_code <= base) {
+                return fallback.lookup(base).map(Variant::Default);
+            }
+        }
+        if let Some(Some(variations)) = self.non_default_variations.get(i) {
+            if let Ok(j) = variations
+                .mappings
+                .binary_search_by_key(&base, |mapping| mapping.code)
+            {
+                return Some(Variant::NonDefault(variations.mappings[j].glyph_id));
+            }
+        }
+        None
+    }
+}
+
+impl Encoding14 {
+    fn from_sequences(sequences: &BTreeMap<(u32, u32), Option<GlyphID>>) -> Self {
+        let mut variations: BTreeMap<u32, (Vec<VariationRange>, Vec<VariationMapping>)> =
+            BTreeMap::new();
+        for (&(base, selector), &glyph_id) in sequences.iter() {
+            let (ranges, mappings) = variations.entry(selector).or_default();
+            match glyph_id {
+                Some(glyph_id) => mappings.push(VariationMapping {
+                    code: base,
+                    glyph_id,
+                }),
+                _ => match ranges.last_mut() {
+                    Some(range)
+                        if range.start_code + range.additional_count as u32 + 1 == base
+                            && range.additional_count < u8::MAX =>
+                    {
+                        range.additional_count += 1
+                    }
+                    _ => ranges.push(VariationRange {
+                        start_code: base,
+                        additional_count: 0,
+                    }),
+                },
+            }
+        }
+        let mut table = Self {
+            format: 14,
+            size: 0,
+            selector_count: variations.len() as _,
+            selectors: vec![],
+            default_variations: vec![],
+            non_default_variations: vec![],
+        };
+        for (character, (ranges, mappings)) in variations {
+            table.selectors.push(VariationSelector {
+                character,
+                default_uvs_offset: 0,
+                non_default_uvs_offset: 0,
+            });
+            table
+                .default_variations
+                .push((!ranges.is_empty()).then_some(DefaultVariations {
+                    range_count: ranges.len() as _,
+                    ranges,
+                }));
+            table
+                .non_default_variations
+                .push((!mappings.is_empty()).then_some(NonDefaultVariations {
+                    mapping_count: mappings.len() as _,
+                    mappings,
+                }));
+        }
+        let (size, offsets) = table.layout();
+        table.size = size;
+        for (selector, (default_uvs_offset, non_default_uvs_offset)) in
+            table.selectors.iter_mut().zip(offsets)
+        {
+            selector.default_uvs_offset = default_uvs_offset;
+            selector.non_default_uvs_offset = non_default_uvs_offset;
+        }
+        table
+    }
+
+    fn layout(&self) -> (u32, Vec<(u32, u32)>) {
+        let mut size = 10 + 11 * self.selectors.len() as u32;
+        let mut offsets = Vec::with_capacity(self.selectors.len());
+        for i in 0..self.selectors.len() {
+            let default_uvs_offset = match self.default_variations.get(i) {
+                Some(Some(variations)) => {
+                    let offset = size;
+                    size += 4 + 4 * variations.ranges.len() as u32;
+                    offset
+                }
+                _ => 0,
+            };
+            let non_default_uvs_offset = match self.non_default_variations.get(i) {
+                Some(Some(variations)) => {
+                    let offset = size;
+                    size += 4 + 5 * variations.mappings.len() as u32;
+                    offset
+                }
+                _ => 0,
+            };
+            offsets.push((default_uvs_offset, non_default_uvs_offset));
+        }
+        (size, offsets)
+    }
+}
+
+impl SequentialGroup {
+    fn lookup(groups: &[Self], code: u32) -> Option<GlyphID> {
+        let i = groups.partition_point(|group| group.end_code < code);
+        let (start_code, end_code) = groups.get(i)?.codes()?;
+        if code < start_code || code > end_code {
+            return None;
+        }
+        Some((groups[i].start_glyph_id + (code - start_code)) as GlyphID)
+            .filter(|&glyph_id| glyph_id != 0)
+    }
+
+    fn ranges(groups: &[Self]) -> Vec<(u32, u32)> {
+        groups
+            .iter()
+            .filter_map(|group| {
+                let (start_code, end_code) = group.codes()?;
+                // A group starting at glyph 0 maps its first character to the
+                // missing glyph.
+                let start_code = match group.start_glyph_id {
+                    0 => start_code.checked_add(1)?,
+                    _ => start_code,
+                };
+                (start_code <= end_code).then_some((start_code, end_code))
+            })
+            .collect()
+    }
+
+    fn iter(groups: &[Self]) -> impl Iterator<Item = (u32, GlyphID)> + '_ {
+        groups.iter().flat_map(|group| {
+            group
+                .codes()
+                .into_iter()
+                .flat_map(move |(start_code, end_code)| {
+                    (start_code..=end_code).map(move |code| {
+                        (
+                            code,
+                            (group.start_glyph_id + (code - start_code)) as GlyphID,
+                        )
+                    })
+                })
+        })
+    }
+
+    // Return the range of characters mapped to glyph IDs that fit in 16 bits.
+    fn codes(&self) -> Option<(u32, u32)> {
+        let room = (GlyphID::MAX as u32).checked_sub(self.start_glyph_id)?;
+        let end_code = self.end_code.min(self.start_code.saturating_add(room));
+        (self.start_code <= end_code).then_some((self.start_code, end_code))
+    }
+}
+
+fn read_u24<T: crate::tape::Read>(tape: &mut T) -> Result<u32> {
+    let buffer: [u8; 3] = tape.take()?;
+    Ok(u32::from_be_bytes([0, buffer[0], buffer[1], buffer[2]]))
+}
+
+fn write_u24<T: crate::tape::Write>(tape: &mut T, value: u32) -> Result<()> {
+    if value > 0xFF_FFFF {
+        raise!("found a malformed character-to-glyph mapping");
+    }
+    let [_, first, second, third] = value.to_be_bytes();
+    tape.give(&[first, second, third])
 }
 
 #[cfg(test)]
@@ -0,0 +1,28 @@
+//! The [control-value program][1].
+//!
+//! [1]: https://learn.microsoft.com/en-us/typography/opentype/spec/prep
+
+use crate::Result;
+
+/// A control-value program.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ControlValueProgram(pub Vec<u8>);
+
+dereference! { ControlValueProgram::0 => [u8] }
+
+impl crate::walue::Read<'static> for ControlValueProgram {
+    type Parameter = usize;
+
+    /// Read the table given its size in bytes.
+    #[inline]
+    fn read<T: crate::tape::Read>(tape: &mut T, size: usize) -> Result<Self> {
+        Ok(Self(tape.take_bytes(size)?))
+    }
+}
+
+impl crate::value::Write for ControlValueProgram {
+    #[inline]
+    fn write<T: crate::tape::Write>(&self, tape: &mut T) -> Result<()> {
+        tape.give_bytes(&self.0)
+    }
+}
@@ -0,0 +1,30 @@
+//! The [control values][1].
+//!
+//! [1]: https://learn.microsoft.com/en-us/typography/opentype/spec/cvt
+
+use crate::Result;
+
+/// Control values.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ControlValues(pub Vec<i16>);
+
+dereference! { ControlValues::0 => [i16] }
+
+impl crate::walue::Read<'static> for ControlValues {
+    type Parameter = usize;
+
+    /// Read the table given its size in bytes.
+    fn read<T: crate::tape::Read>(tape: &mut T, size: usize) -> Result<Self> {
+        if !size.is_multiple_of(2) {
+            raise!("found a malformed control-value table");
+        }
+        Ok(Self(tape.take_given(size / 2)?))
+    }
+}
+
+impl crate::value::Write for ControlValues {
+    #[inline]
+    fn write<T: crate::tape::Write>(&self, tape: &mut T) -> Result<()> {
+        tape.give(&self.0[..])
+    }
+}
@@ -0,0 +1,156 @@
+//! The [digital signature][1].
+//!
+//! The signatures are kept as raw PKCS#7 data, and they are not verified.
+//!
+//! [1]: https://learn.microsoft.com/en-us/typography/opentype/spec/dsig
+
+use crate::Result;
+
+table! {
+    @position
+    /// A digital signature.
+    pub DigitalSignature { // DSIG
+        version         (u32  ) = { 1 }, // version
+        signature_count (u16  ), // numSignatures
+        flags           (Flags), // flags
+
+        records (Vec<Record>) |this, tape, _| { // signatureRecords
+            tape.take_given(this.signature_count as usize)
+        },
+
+        signatures (Vec<Signature>) |this, tape, position| {
+            let mut values = Vec::with_capacity(this.records.len());
+            for record in this.records.iter() {
+                tape.jump(position + record.offset as u64)?;
+                values.push(tape.take_given((record.format, record.size))?);
+            }
+            Ok(values)
+        },
+    }
+}
+
+flags! {
+    /// Digital-signature flags.
+    pub Flags(u16) {
+        0b0000_0000_0000_0001 => cannot_be_resigned,
+        0b1111_1111_1111_1110 => is_invalid,
+    }
+}
+
+table! {
+    @write
+    /// A signature record.
+    #[derive(Copy)]
+    pub Record { // SignatureRecord
+        format (u32), // format
+        size   (u32), // length
+        offset (u32), // signatureBlockOffset
+    }
+}
+
+/// A signature.
+#[derive(Clone, Debug)]
+pub enum Signature {
+    /// Format 1.
+    Format1(Signature1),
+    /// An unknown format with its raw data.
+    Unknown(u32, Vec<u8>),
+}
+
+table! {
+    @write
+    /// A signature in format 1.
+    pub Signature1 { // SignatureBlockFormat1
+        reserved1 (u16) = { 0 }, // reserved1
+        reserved2 (u16) = { 0 }, // reserved2
+        size      (u32), // signatureLength
+
+        data (Vec<u8>) |this, tape| { // signature
+            tape.take_bytes(this.size as usize)
+        },
+    }
+}
+
+impl DigitalSignature {
+    /// Create an empty table of version 1 without any signatures.
+    ///
+    /// Such a stub is expected to be present by some applications.
+    pub fn stub() -> Self {
+        Self {
+            version: 1,
+            ..Default::default()
+        }
+    }
+}
+
+impl Signature {
+    /// Return the format.
+    pub fn format(&self) -> u32 {
+        match self {
+            Signature::Format1(_) => 1,
+            Signature::Unknown(format, _) => *format,
+        }
+    }
+
+    /// Return the raw PKCS#7 data.
+    pub fn data(&self) -> &[u8] {
+        match self {
+            Signature::Format1(signature) => &signature.data,
+            Signature::Unknown(_, data) => data,
+        }
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            Signature::Format1(signature) => 8 + signature.data.len(),
+            Signature::Unknown(_, data) => data.len(),
+        }
+    }
+}
+
+impl crate::value::Write for DigitalSignature {
+    fn write<T: crate::tape::Write>(&self, tape: &mut T) -> Result<()> {
+        if self.signatures.len() > u16::MAX as usize {
+            raise!("found a malformed digital signature");
+        }
+        tape.give(&self.version)?;
+        tape.give(&(self.signatures.len() as u16))?;
+        tape.give(&self.flags)?;
+        let mut offset = 8 + 12 * self.signatures.len();
+        for signature in self.signatures.iter() {
+            let size = signature.size();
+            if offset + size > u32::MAX as usize {
+                raise!("found a malformed digital signature");
+            }
+            tape.give(&Record {
+                format: signature.format(),
+                size: size as u32,
+                offset: offset as u32,
+            })?;
+            offset += size;
+        }
+        for signature in self.signatures.iter() {
+            match signature {
+                Signature::Format1(signature) => {
+                    if signature.data.len() != signature.size as usize {
+                        raise!("found a malformed digital signature");
+                    }
+                    tape.give(signature)?;
+                }
+                Signature::Unknown(_, data) => tape.give_bytes(data)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl crate::walue::Read<'static> for Signature {
+    type Parameter = (u32, u32);
+
+    fn read<T: crate::tape::Read>(tape: &mut T, (format, size): Self::Parameter) -> Result<Self> {
+        Ok(match format {
+            1 => Signature::Format1(tape.take()?),
+            _ => Signature::Unknown(format, tape.take_bytes(size as usize)?),
+        })
+    }
+}
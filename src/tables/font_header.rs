@@ -65,6 +65,10 @@ impl FontHeader {
     /// The magic number for computing the checksum adjustment.
     pub const CHECKSUM_ADJUSTMENT: u32 = 0xB1B0AFBA;
 
+    /// The bytes of the checksum adjustment within the table, which follows the
+    /// version and the font revision.
+    pub(crate) const CHECKSUM_ADJUSTMENT_RANGE: std::ops::Range<usize> = 8..12;
+
     /// Compute the checksum.
     pub fn checksum<T: crate::tape::Read>(tape: &mut T) -> Result<u32> {
         let mut data = vec![];
@@ -0,0 +1,28 @@
+//! The [font program][1].
+//!
+//! [1]: https://learn.microsoft.com/en-us/typography/opentype/spec/fpgm
+
+use crate::Result;
+
+/// A font program.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FontProgram(pub Vec<u8>);
+
+dereference! { FontProgram::0 => [u8] }
+
+impl crate::walue::Read<'static> for FontProgram {
+    type Parameter = usize;
+
+    /// Read the table given its size in bytes.
+    #[inline]
+    fn read<T: crate::tape::Read>(tape: &mut T, size: usize) -> Result<Self> {
+        Ok(Self(tape.take_bytes(size)?))
+    }
+}
+
+impl crate::value::Write for FontProgram {
+    #[inline]
+    fn write<T: crate::tape::Write>(&self, tape: &mut T) -> Result<()> {
+        tape.give_bytes(&self.0)
+    }
+}
@@ -3,14 +3,18 @@
 //! [1]: https://learn.microsoft.com/en-us/typography/opentype/spec/glyf
 
 use crate::tables::glyph_mapping::GlyphMapping;
-use crate::{q16, GlyphID, Result};
+use crate::{q16, GlyphID, Result, ALIGNMENT};
 
 /// Glyph data.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct GlyphData(pub Vec<Option<Glyph>>);
 
 table! {
     /// A glyph.
+    ///
+    /// The number of contours has to be that of the end points of a simple
+    /// description or -1 for a composite one, which is checked when writing.
+    #[derive(PartialEq)]
     pub Glyph { // Glyph Header
         contour_count (i16), // numberOfContours
         min_x         (i16), // xMin
@@ -25,7 +29,7 @@ table! {
 }
 
 /// A glyph description.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Description {
     /// A simple-glyph description.
     Simple(SimpleDescription),
@@ -36,6 +40,7 @@ pub enum Description {
 table! {
     @define
     /// A simple-glyph description.
+    #[derive(PartialEq)]
     pub SimpleDescription { // Simple Glyph Table
         end_points       (Vec<u16>       ), // endPtsOfContours
         instruction_size (u16            ), // instructionLength
@@ -47,7 +52,7 @@ table! {
 }
 
 /// A composite-glyph description.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct CompositeDescription {
     pub components: Vec<Component>,
     pub instruction_size: u16,
@@ -56,7 +61,7 @@ pub struct CompositeDescription {
 
 table! {
     /// A component of a composite glyph.
-    #[derive(Copy)]
+    #[derive(Copy, PartialEq)]
     pub Component { // Component Glyph Table
         flags    (ComponentFlags), // flags
         glyph_id (GlyphID       ), // glyphIndex
@@ -74,39 +79,59 @@ table! {
 flags! {
     /// Point flags.
     pub PointFlags(u8) { // Simple Glyph Flags
-        0b0000_0001 => is_on_curve,
-        0b0000_0010 => is_x_short,
-        0b0000_0100 => is_y_short,
-        0b0000_1000 => is_repeated,
-        0b0001_0000 => is_x_positive,
-        0b0001_0000 => is_x_same,
-        0b0010_0000 => is_y_positive,
-        0b0010_0000 => is_y_same,
-        0b0100_0000 => is_overlap_simple,
-        0b1000_0000 => is_invalid,
+        PointFlags::ON_CURVE           => is_on_curve,
+        PointFlags::X_SHORT            => is_x_short,
+        PointFlags::Y_SHORT            => is_y_short,
+        PointFlags::REPEATED           => is_repeated,
+        PointFlags::X_POSITIVE_OR_SAME => is_x_positive,
+        PointFlags::X_POSITIVE_OR_SAME => is_x_same,
+        PointFlags::Y_POSITIVE_OR_SAME => is_y_positive,
+        PointFlags::Y_POSITIVE_OR_SAME => is_y_same,
+        PointFlags::OVERLAP_SIMPLE     => is_overlap_simple,
+        0b1000_0000                    => is_invalid,
     }
 }
 
+impl PointFlags {
+    const ON_CURVE: u8 = 0b0000_0001;
+    const X_SHORT: u8 = 0b0000_0010;
+    const Y_SHORT: u8 = 0b0000_0100;
+    const REPEATED: u8 = 0b0000_1000;
+    const X_POSITIVE_OR_SAME: u8 = 0b0001_0000;
+    const Y_POSITIVE_OR_SAME: u8 = 0b0010_0000;
+    const OVERLAP_SIMPLE: u8 = 0b0100_0000;
+}
+
 flags! {
     @define
     /// Component flags.
     pub ComponentFlags(u16) { // Component Glyph Flags
-        0b0000_0000_0000_0001 => are_arguments_words,
-        0b0000_0000_0000_0010 => are_arguments_xy,
-        0b0000_0000_0000_0100 => should_round_xy_to_grid,
-        0b0000_0000_0000_1000 => has_scalar_scale,
-        0b0000_0000_0010_0000 => has_more_components,
-        0b0000_0000_0100_0000 => has_vector_scale,
-        0b0000_0000_1000_0000 => has_matrix_scale,
-        0b0000_0001_0000_0000 => has_instructions,
-        0b0000_0010_0000_0000 => should_use_metrics,
-        0b0000_0100_0000_0000 => has_overlap,
-        0b0000_1000_0000_0000 => is_offset_scaled,
-        0b0001_0000_0000_0000 => is_offset_unscaled,
-        0b1110_0000_0001_0000 => is_invalid,
+        ComponentFlags::ARE_ARGUMENTS_WORDS => are_arguments_words,
+        ComponentFlags::ARE_ARGUMENTS_XY    => are_arguments_xy,
+        0b0000_0000_0000_0100               => should_round_xy_to_grid,
+        ComponentFlags::HAS_SCALAR_SCALE    => has_scalar_scale,
+        ComponentFlags::HAS_MORE_COMPONENTS => has_more_components,
+        ComponentFlags::HAS_VECTOR_SCALE    => has_vector_scale,
+        ComponentFlags::HAS_MATRIX_SCALE    => has_matrix_scale,
+        ComponentFlags::HAS_INSTRUCTIONS    => has_instructions,
+        0b0000_0010_0000_0000               => should_use_metrics,
+        0b0000_0100_0000_0000               => has_overlap,
+        0b0000_1000_0000_0000               => is_offset_scaled,
+        0b0001_0000_0000_0000               => is_offset_unscaled,
+        0b1110_0000_0001_0000               => is_invalid,
     }
 }
 
+impl ComponentFlags {
+    const ARE_ARGUMENTS_WORDS: u16 = 0b0000_0000_0000_0001;
+    const ARE_ARGUMENTS_XY: u16 = 0b0000_0000_0000_0010;
+    const HAS_SCALAR_SCALE: u16 = 0b0000_0000_0000_1000;
+    const HAS_MORE_COMPONENTS: u16 = 0b0000_0000_0010_0000;
+    const HAS_VECTOR_SCALE: u16 = 0b0000_0000_0100_0000;
+    const HAS_MATRIX_SCALE: u16 = 0b0000_0000_1000_0000;
+    const HAS_INSTRUCTIONS: u16 = 0b0000_0001_0000_0000;
+}
+
 #[cfg(not(feature = "ignore-invalid-component-flags"))]
 flags!(@read pub ComponentFlags(u16));
 
@@ -119,7 +144,7 @@ impl crate::value::Read for ComponentFlags {
 }
 
 /// Arguments of a component.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Arguments {
     /// Offsets relative to the current point.
     Offsets(i16, i16),
@@ -128,7 +153,7 @@ pub enum Arguments {
 }
 
 /// Options of a component.
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub enum Options {
     /// No options.
     #[default]
@@ -192,6 +217,55 @@ impl<'l> crate::walue::Read<'l> for GlyphData {
     }
 }
 
+impl GlyphData {
+    /// Write the table and return the corresponding glyph-to-location mapping.
+    ///
+    /// Each glyph is padded to a 4-byte boundary, and the mapping is given in
+    /// the short format whenever the offsets permit it. The caller has to set
+    /// `glyph_mapping_format` in the font header to 0 for `HalfOffsets` and to
+    /// 1 for `Offsets` accordingly.
+    pub fn write<T: crate::tape::Write>(&self, tape: &mut T) -> Result<GlyphMapping> {
+        use crate::tape::Write;
+
+        let mut data = vec![];
+        let mut offsets = Vec::with_capacity(self.0.len() + 1);
+        offsets.push(0);
+        for glyph in self.0.iter() {
+            if let Some(glyph) = glyph {
+                data.give(glyph)?;
+                data.resize(data.len().next_multiple_of(ALIGNMENT), 0);
+            }
+            offsets.push(data.len());
+        }
+        tape.give_bytes(&data)?;
+        Ok(if data.len() / 2 <= u16::MAX as usize {
+            GlyphMapping::HalfOffsets(offsets.iter().map(|&offset| (offset / 2) as _).collect())
+        } else if data.len() <= u32::MAX as usize {
+            GlyphMapping::Offsets(offsets.iter().map(|&offset| offset as _).collect())
+        } else {
+            raise!("found too much glyph data to map")
+        })
+    }
+}
+
+impl crate::value::Write for Glyph {
+    fn write<T: crate::tape::Write>(&self, tape: &mut T) -> Result<()> {
+        let contour_count = match &self.description {
+            Description::Simple(description) => i16::try_from(description.end_points.len()).ok(),
+            Description::Composite(_) => Some(-1),
+        };
+        if contour_count != Some(self.contour_count) {
+            raise!("found a malformed glyph");
+        }
+        tape.give(&self.contour_count)?;
+        tape.give(&self.min_x)?;
+        tape.give(&self.min_y)?;
+        tape.give(&self.max_x)?;
+        tape.give(&self.max_y)?;
+        tape.give(&self.description)
+    }
+}
+
 impl Default for Description {
     #[inline]
     fn default() -> Self {
@@ -235,6 +309,15 @@ impl crate::walue::Read<'static> for Description {
     }
 }
 
+impl crate::value::Write for Description {
+    fn write<T: crate::tape::Write>(&self, tape: &mut T) -> Result<()> {
+        match self {
+            Description::Simple(value) => tape.give(value),
+            Description::Composite(value) => tape.give(value),
+        }
+    }
+}
+
 impl crate::walue::Read<'static> for SimpleDescription {
     type Parameter = usize;
 
@@ -302,6 +385,146 @@ impl crate::walue::Read<'static> for SimpleDescription {
     }
 }
 
+impl crate::value::Write for SimpleDescription {
+    fn write<T: crate::tape::Write>(&self, tape: &mut T) -> Result<()> {
+        macro_rules! reject(() => (raise!("found a malformed glyph description")));
+
+        let point_count = self.end_points.last().map(|&i| i as usize + 1).unwrap_or(0);
+        if self.flags.len() != point_count
+            || self.x.len() != point_count
+            || self.y.len() != point_count
+        {
+            reject!();
+        }
+        if self.instructions.len() > u16::MAX as usize {
+            reject!();
+        }
+        tape.give(&*self.end_points)?;
+        tape.give(&(self.instructions.len() as u16))?;
+        tape.give_bytes(&self.instructions)?;
+
+        let flags = (0..point_count)
+            .map(|i| {
+                PointFlags::encode(
+                    self.flags[i].0 & (PointFlags::ON_CURVE | PointFlags::OVERLAP_SIMPLE),
+                    self.x[i],
+                    self.y[i],
+                )
+            })
+            .collect::<Vec<_>>();
+        let mut i = 0;
+        while i < point_count {
+            let mut count = 1;
+            while i + count < point_count && flags[i + count] == flags[i] && count <= 255 {
+                count += 1;
+            }
+            if count > 2 {
+                tape.give(&(flags[i].0 | PointFlags::REPEATED))?;
+                tape.give(&((count - 1) as u8))?;
+            } else {
+                count = 1;
+                tape.give(&flags[i])?;
+            }
+            i += count;
+        }
+
+        macro_rules! write_coordinates(
+            ($values:expr, $is_short:ident, $is_same:ident) => ({
+                for (value, flag) in $values.iter().zip(flags.iter()) {
+                    if flag.$is_short() {
+                        tape.give(&(value.unsigned_abs() as u8))?;
+                    } else if !flag.$is_same() {
+                        tape.give(value)?;
+                    }
+                }
+            });
+        );
+        write_coordinates!(self.x, is_x_short, is_x_same);
+        write_coordinates!(self.y, is_y_short, is_y_same);
+        Ok(())
+    }
+}
+
+impl crate::value::Write for CompositeDescription {
+    fn write<T: crate::tape::Write>(&self, tape: &mut T) -> Result<()> {
+        if self.components.is_empty() || self.instructions.len() > u16::MAX as usize {
+            raise!("found a malformed glyph description");
+        }
+        let has_instructions = !self.instructions.is_empty()
+            || self
+                .components
+                .iter()
+                .any(|component| component.flags.has_instructions());
+        let last = self.components.len() - 1;
+        for (i, component) in self.components.iter().enumerate() {
+            let mut flags = component.flags.0
+                & !(ComponentFlags::ARE_ARGUMENTS_WORDS
+                    | ComponentFlags::ARE_ARGUMENTS_XY
+                    | ComponentFlags::HAS_SCALAR_SCALE
+                    | ComponentFlags::HAS_MORE_COMPONENTS
+                    | ComponentFlags::HAS_VECTOR_SCALE
+                    | ComponentFlags::HAS_MATRIX_SCALE
+                    | ComponentFlags::HAS_INSTRUCTIONS);
+            match component.arguments {
+                Arguments::Offsets(x, y) => {
+                    flags |= ComponentFlags::ARE_ARGUMENTS_XY;
+                    if i8::try_from(x).is_err() || i8::try_from(y).is_err() {
+                        flags |= ComponentFlags::ARE_ARGUMENTS_WORDS;
+                    }
+                }
+                Arguments::Indices(i, j) => {
+                    if u8::try_from(i).is_err() || u8::try_from(j).is_err() {
+                        flags |= ComponentFlags::ARE_ARGUMENTS_WORDS;
+                    }
+                }
+            }
+            match component.options {
+                Options::None => {}
+                Options::Scalar(..) => flags |= ComponentFlags::HAS_SCALAR_SCALE,
+                Options::Vector(..) => flags |= ComponentFlags::HAS_VECTOR_SCALE,
+                Options::Matrix(..) => flags |= ComponentFlags::HAS_MATRIX_SCALE,
+            }
+            if i < last {
+                flags |= ComponentFlags::HAS_MORE_COMPONENTS;
+            } else if has_instructions {
+                flags |= ComponentFlags::HAS_INSTRUCTIONS;
+            }
+            let flags = ComponentFlags(flags);
+            tape.give(&flags)?;
+            tape.give(&component.glyph_id)?;
+            crate::walue::Write::write(&component.arguments, tape, flags)?;
+            tape.give(&component.options)?;
+        }
+        if has_instructions {
+            tape.give(&(self.instructions.len() as u16))?;
+            tape.give_bytes(&self.instructions)?;
+        }
+        Ok(())
+    }
+}
+
+impl PointFlags {
+    fn encode(base: u8, x: i16, y: i16) -> Self {
+        macro_rules! encode(
+            ($value:ident, $short:ident, $positive_or_same:ident) => (
+                if $value == 0 {
+                    Self::$positive_or_same
+                } else if $value.unsigned_abs() <= u8::MAX as u16 {
+                    Self::$short | if $value > 0 { Self::$positive_or_same } else { 0 }
+                } else {
+                    0
+                }
+            );
+        );
+        Self(
+            base | encode!(x, X_SHORT, X_POSITIVE_OR_SAME)
+                | encode!(y, Y_SHORT, Y_POSITIVE_OR_SAME),
+        )
+    }
+}
+
+flags!(@write pub ComponentFlags(u16));
+
 impl Default for Arguments {
     #[inline]
     fn default() -> Self {
@@ -338,6 +561,32 @@ impl crate::walue::Read<'static> for Arguments {
     }
 }
 
+impl crate::walue::Write<'static> for Arguments {
+    type Parameter = ComponentFlags;
+
+    fn write<T: crate::tape::Write>(&self, tape: &mut T, flags: ComponentFlags) -> Result<()> {
+        match (*self, flags.are_arguments_words(), flags.are_arguments_xy()) {
+            (Arguments::Offsets(x, y), true, true) => {
+                tape.give(&x)?;
+                tape.give(&y)
+            }
+            (Arguments::Offsets(x, y), false, true) => {
+                tape.give(&(x as i8))?;
+                tape.give(&(y as i8))
+            }
+            (Arguments::Indices(i, j), true, false) => {
+                tape.give(&i)?;
+                tape.give(&j)
+            }
+            (Arguments::Indices(i, j), false, false) => {
+                tape.give(&(i as u8))?;
+                tape.give(&(j as u8))
+            }
+            _ => raise!("found malformed arguments of a component"),
+        }
+    }
+}
+
 impl crate::walue::Read<'static> for Options {
     type Parameter = ComponentFlags;
 
@@ -358,3 +607,22 @@ impl crate::walue::Read<'static> for Options {
         }
     }
 }
+
+impl crate::value::Write for Options {
+    fn write<T: crate::tape::Write>(&self, tape: &mut T) -> Result<()> {
+        match *self {
+            Options::None => Ok(()),
+            Options::Scalar(value) => tape.give(&value),
+            Options::Vector(x, y) => {
+                tape.give(&x)?;
+                tape.give(&y)
+            }
+            Options::Matrix(xx, xy, yx, yy) => {
+                tape.give(&xx)?;
+                tape.give(&xy)?;
+                tape.give(&yx)?;
+                tape.give(&yy)
+            }
+        }
+    }
+}
@@ -30,3 +30,12 @@ impl<'l> crate::walue::Read<'l> for GlyphMapping {
         }
     }
 }
+
+impl crate::value::Write for GlyphMapping {
+    fn write<T: crate::tape::Write>(&self, tape: &mut T) -> Result<()> {
+        match self {
+            GlyphMapping::HalfOffsets(offsets) => tape.give(&offsets[..]),
+            GlyphMapping::Offsets(offsets) => tape.give(&offsets[..]),
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! The [grid-fitting and scan-conversion procedure][1].
+//!
+//! [1]: https://learn.microsoft.com/en-us/typography/opentype/spec/gasp
+
+table! {
+    @write
+    /// A grid-fitting and scan-conversion procedure.
+    pub GridFitting {
+        version (u16) |_, tape| { // version
+            let value = tape.take()?;
+            if value > 1 {
+                raise!("found an unknown version of the grid-fitting and scan-conversion procedure");
+            }
+            Ok(value)
+        },
+
+        range_count (u16), // numRanges
+
+        ranges (Vec<Range>) |this, tape| { // gaspRanges
+            tape.take_given(this.range_count as usize)
+        },
+    }
+}
+
+table! {
+    @write
+    /// A range of sizes.
+    #[derive(Copy)]
+    pub Range { // GaspRange
+        max_ppem (u16     ), // rangeMaxPPEM
+        behavior (Behavior), // rangeGaspBehavior
+    }
+}
+
+flags! {
+    /// Rendering behavior flags.
+    pub Behavior(u16) {
+        0b0000_0000_0000_0001 => should_grid_fit,
+        0b0000_0000_0000_0010 => should_do_gray,
+        0b0000_0000_0000_0100 => should_grid_fit_symmetrically,
+        0b0000_0000_0000_1000 => should_smooth_symmetrically,
+        0b1111_1111_1111_0000 => is_invalid,
+    }
+}
+
+impl GridFitting {
+    /// Create an instance from ranges given by the upper limit in pixels per em
+    /// and the corresponding behavior.
+    ///
+    /// The version is 1 if any of the symmetric flags is set and 0 otherwise.
+    pub fn new<T>(ranges: T) -> Self
+    where
+        T: IntoIterator<Item = (u16, Behavior)>,
+    {
+        let ranges = ranges
+            .into_iter()
+            .map(|(max_ppem, behavior)| Range { max_ppem, behavior })
+            .collect::<Vec<_>>();
+        let symmetric = ranges.iter().any(|range| {
+            range.behavior.should_grid_fit_symmetrically()
+                || range.behavior.should_smooth_symmetrically()
+        });
+        Self {
+            version: if symmetric { 1 } else { 0 },
+            range_count: ranges.len() as _,
+            ranges,
+        }
+    }
+
+    /// Return the behavior at a size in pixels per em.
+    ///
+    /// The symmetric flags are ignored in version 0.
+    pub fn behavior_at(&self, ppem: u16) -> Option<Behavior> {
+        let range = self.ranges.iter().find(|range| ppem <= range.max_ppem)?;
+        Some(match self.version {
+            0 => Behavior(range.behavior.0 & 0b11),
+            _ => range.behavior,
+        })
+    }
+}
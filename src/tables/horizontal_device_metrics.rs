@@ -0,0 +1,146 @@
+//! The [horizontal device metrics][1].
+//!
+//! [1]: https://learn.microsoft.com/en-us/typography/opentype/spec/hdmx
+
+use crate::tables::maximum_profile::MaximumProfile;
+use crate::{GlyphID, Result};
+
+table! {
+    @define
+    /// Horizontal device metrics.
+    pub HorizontalDeviceMetrics {
+        version      (u16        ), // version
+        record_count (i16        ), // numRecords
+        record_size  (i32        ), // sizeDeviceRecord
+        records      (Vec<Record>), // records
+    }
+}
+
+table! {
+    @define
+    /// A record of horizontal device metrics.
+    pub Record { // DeviceRecord
+        pixel_size (u8     ), // pixelSize
+        max_width  (u8     ), // maxWidth
+        widths     (Vec<u8>), // widths
+    }
+}
+
+impl HorizontalDeviceMetrics {
+    /// Return the advance width of a glyph in pixels at a size in pixels per em
+    /// if present.
+    pub fn width(&self, ppem: u8, glyph_id: GlyphID) -> Option<u8> {
+        self.records
+            .iter()
+            .find(|record| record.pixel_size == ppem)
+            .and_then(|record| record.widths.get(glyph_id as usize).copied())
+    }
+}
+
+impl FromIterator<(u8, Vec<u8>)> for HorizontalDeviceMetrics {
+    /// Create an instance from sizes in pixels per em and the advance widths of
+    /// all glyphs in pixels at each size, such as the ones grid-fitted by a
+    /// hinting machine.
+    ///
+    /// The records are sorted by size, and the counts and maximum widths are
+    /// computed.
+    fn from_iter<T>(widths: T) -> Self
+    where
+        T: IntoIterator<Item = (u8, Vec<u8>)>,
+    {
+        let mut records = widths
+            .into_iter()
+            .map(|(pixel_size, widths)| Record {
+                pixel_size,
+                max_width: widths.iter().copied().max().unwrap_or(0),
+                widths,
+            })
+            .collect::<Vec<_>>();
+        records.sort_by_key(|record| record.pixel_size);
+        let glyph_count = records
+            .first()
+            .map(|record| record.widths.len())
+            .unwrap_or(0);
+        Self {
+            version: 0,
+            record_count: records.len() as i16,
+            record_size: size(glyph_count) as i32,
+            records,
+        }
+    }
+}
+
+impl<'l> crate::walue::Read<'l> for HorizontalDeviceMetrics {
+    type Parameter = &'l MaximumProfile;
+
+    fn read<T: crate::tape::Read>(tape: &mut T, profile: Self::Parameter) -> Result<Self> {
+        let position = tape.position()?;
+        let version = tape.take()?;
+        if version != 0 {
+            raise!("found an unknown version of the horizontal device metrics");
+        }
+        let record_count = tape.take::<i16>()?;
+        let record_size = tape.take::<i32>()?;
+        let glyph_count = profile.glyph_count();
+        if record_count < 0 || record_size < 0 || (record_size as usize) < glyph_count + 2 {
+            raise!("found malformed horizontal device metrics");
+        }
+        let mut records = Vec::with_capacity(record_count as usize);
+        for i in 0..(record_count as u64) {
+            tape.jump(position + 8 + i * record_size as u64)?;
+            records.push(tape.take_given(glyph_count)?);
+        }
+        Ok(Self {
+            version,
+            record_count,
+            record_size,
+            records,
+        })
+    }
+}
+
+impl crate::value::Write for HorizontalDeviceMetrics {
+    fn write<T: crate::tape::Write>(&self, tape: &mut T) -> Result<()> {
+        let glyph_count = self
+            .records
+            .first()
+            .map(|record| record.widths.len())
+            .unwrap_or(0);
+        if self.records.len() > i16::MAX as usize
+            || self
+                .records
+                .iter()
+                .any(|record| record.widths.len() != glyph_count)
+        {
+            raise!("found malformed horizontal device metrics");
+        }
+        let record_size = size(glyph_count);
+        tape.give(&self.version)?;
+        tape.give(&(self.records.len() as i16))?;
+        tape.give(&(record_size as i32))?;
+        for record in self.records.iter() {
+            tape.give(&record.pixel_size)?;
+            tape.give(&record.max_width)?;
+            tape.give_bytes(&record.widths)?;
+            tape.give_bytes(&vec![0; record_size - 2 - glyph_count])?;
+        }
+        Ok(())
+    }
+}
+
+impl crate::walue::Read<'static> for Record {
+    type Parameter = usize;
+
+    fn read<T: crate::tape::Read>(tape: &mut T, glyph_count: usize) -> Result<Self> {
+        Ok(Self {
+            pixel_size: tape.take()?,
+            max_width: tape.take()?,
+            widths: tape.take_bytes(glyph_count)?,
+        })
+    }
+}
+
+#[inline]
+fn size(glyph_count: usize) -> usize {
+    (2 + glyph_count + 3) & !3
+}
@@ -2,7 +2,11 @@
 //!
 //! [1]: https://learn.microsoft.com/en-us/typography/opentype/spec/hhea
 
+use crate::tables::horizontal_metrics::HorizontalMetrics;
+use crate::tables::metrics::summarize;
+
 table! {
+    @write
     /// A horizontal header.
     #[derive(Copy)]
     pub HorizontalHeader {
@@ -26,3 +30,27 @@ table! {
         horizontal_metric_count (u16), // numberOfHMetrics
     }
 }
+
+impl HorizontalHeader {
+    /// Update the fields derived from horizontal metrics.
+    ///
+    /// The bounds are the minimum and maximum x coordinates of each glyph, or
+    /// `None` for glyphs without contours, which are ignored when computing the
+    /// side bearings and the extent.
+    pub fn update<T>(&mut self, metrics: &HorizontalMetrics, bounds: T)
+    where
+        T: IntoIterator<Item = Option<(i16, i16)>>,
+    {
+        self.horizontal_metric_count = metrics.records.len() as _;
+        self.max_advance_width = metrics
+            .records
+            .iter()
+            .map(|record| record.advance_width)
+            .max()
+            .unwrap_or(0);
+        let (left, right, extent) = summarize(bounds, |glyph_id| metrics.get(glyph_id));
+        self.min_left_side_bearing = left;
+        self.min_right_side_bearing = right;
+        self.max_x_extent = extent;
+    }
+}
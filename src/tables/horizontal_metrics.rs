@@ -4,6 +4,7 @@
 
 use crate::tables::horizontal_header::HorizontalHeader;
 use crate::tables::maximum_profile::MaximumProfile;
+use crate::tables::metrics;
 use crate::{GlyphID, Result};
 
 table! {
@@ -16,6 +17,7 @@ table! {
 }
 
 table! {
+    @write
     /// A record of horizontal metrics.
     #[derive(Copy)]
     pub Record { // longHorMetric
@@ -26,28 +28,40 @@ table! {
 
 impl HorizontalMetrics {
     /// Return the advance width and left side bearing.
+    ///
+    /// Glyphs beyond the end of the table take the last advance width and the
+    /// last left side bearing.
     pub fn get(&self, glyph_id: GlyphID) -> (u16, i16) {
-        let mut index = glyph_id as usize;
-        let longs = self.records.len();
-        if index < longs {
-            (
-                self.records[index].advance_width,
-                self.records[index].left_side_bearing,
-            )
-        } else {
-            let shorts = self.left_side_bearings.len();
-            index -= longs;
-            if index < shorts {
-                (
-                    self.records[longs - 1].advance_width,
-                    self.left_side_bearings[index],
-                )
-            } else {
-                (
-                    self.records[longs - 1].advance_width,
-                    self.left_side_bearings[shorts - 1],
-                )
-            }
+        metrics::get(
+            &self.records,
+            &self.left_side_bearings,
+            glyph_id,
+            |record| (record.advance_width, record.left_side_bearing),
+        )
+    }
+}
+
+impl FromIterator<(u16, i16)> for HorizontalMetrics {
+    /// Create an instance from the advance widths and left side bearings of
+    /// all glyphs.
+    ///
+    /// The trailing glyphs sharing the advance width of the last glyph are
+    /// stored with left side bearings only. An empty iterator gives a single
+    /// zero record, as the table needs at least one.
+    fn from_iter<T>(metrics: T) -> Self
+    where
+        T: IntoIterator<Item = (u16, i16)>,
+    {
+        let (records, left_side_bearings) = metrics::compact(metrics);
+        Self {
+            records: records
+                .into_iter()
+                .map(|(advance_width, left_side_bearing)| Record {
+                    advance_width,
+                    left_side_bearing,
+                })
+                .collect(),
+            left_side_bearings,
         }
     }
 }
@@ -78,3 +92,10 @@ impl<'l> crate::walue::Read<'l> for HorizontalMetrics {
         Ok(table)
     }
 }
+
+impl crate::value::Write for HorizontalMetrics {
+    fn write<T: crate::tape::Write>(&self, tape: &mut T) -> Result<()> {
+        tape.give(&self.records[..])?;
+        tape.give(&self.left_side_bearings[..])
+    }
+}
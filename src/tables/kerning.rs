@@ -0,0 +1,381 @@
+//! The [kerning table][1].
+//!
+//! Both the Microsoft variant of version 0 and the [Apple variant][2] of
+//! version 1 are supported.
+//!
+//! [1]: https://learn.microsoft.com/en-us/typography/opentype/spec/kern
+//! [2]: https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6kern.html
+
+use crate::{GlyphID, Result};
+
+/// A kerning table.
+#[derive(Clone, Debug)]
+pub enum Kerning {
+    /// Version 0.
+    Version0(Kerning0),
+    /// Version 1.
+    Version1(Kerning1),
+}
+
+table! {
+    /// A kerning table of version 0.
+    pub Kerning0 {
+        version     (u16) = { 0 }, // version
+        table_count (u16), // nTables
+
+        tables (Vec<Subtable0>) |this, tape| { // subtables
+            tape.take_given(this.table_count as usize)
+        },
+    }
+}
+
+table! {
+    /// A kerning table of version 1.
+    pub Kerning1 {
+        version     (u32) = { 0x00010000 }, // version
+        table_count (u32), // nTables
+
+        tables (Vec<Subtable1>) |this, tape| { // subtables
+            tape.take_given(this.table_count as usize)
+        },
+    }
+}
+
+/// A subtable of a kerning table of version 0.
+#[derive(Clone, Debug)]
+pub struct Subtable0 {
+    pub header: Header0,
+    pub format: Format,
+}
+
+/// A subtable of a kerning table of version 1.
+#[derive(Clone, Debug)]
+pub struct Subtable1 {
+    pub header: Header1,
+    pub format: Format,
+}
+
+table! {
+    /// The header of a subtable of a kerning table of version 0.
+    #[derive(Copy)]
+    pub Header0 {
+        version  (u16      ), // version
+        size     (u16      ), // length
+        coverage (Coverage0), // coverage
+    }
+}
+
+table! {
+    /// The header of a subtable of a kerning table of version 1.
+    #[derive(Copy)]
+    pub Header1 {
+        size        (u32      ), // length
+        coverage    (Coverage1), // coverage
+        tuple_index (u16      ), // tupleIndex
+    }
+}
+
+flags! {
+    /// Coverage flags of a subtable of a kerning table of version 0.
+    pub Coverage0(u16) {
+        0b0000_0000_0000_0001 => is_horizontal,
+        0b0000_0000_0000_0010 => has_minimum_values,
+        0b0000_0000_0000_0100 => is_cross_stream,
+        0b0000_0000_0000_1000 => is_override,
+        0b0000_0000_1111_0000 => is_invalid,
+    }
+}
+
+flags! {
+    /// Coverage flags of a subtable of a kerning table of version 1.
+    pub Coverage1(u16) {
+        0b1000_0000_0000_0000 => is_vertical,
+        0b0100_0000_0000_0000 => is_cross_stream,
+        0b0010_0000_0000_0000 => has_variations,
+        0b0001_1111_0000_0000 => is_invalid,
+    }
+}
+
+/// A format of a kerning subtable.
+#[derive(Clone, Debug)]
+pub enum Format {
+    /// Format 0.
+    Format0(Format0),
+    /// Format 2.
+    Format2(Format2),
+    /// Format 3.
+    Format3(Format3),
+    /// An unknown format.
+    Unknown(u8),
+}
+
+table! {
+    /// A kerning subtable in format 0.
+    pub Format0 {
+        pair_count     (u16), // nPairs
+        search_range   (u16), // searchRange
+        entry_selector (u16), // entrySelector
+        range_shift    (u16), // rangeShift
+
+        pairs (Vec<Pair>) |this, tape| { // kerningPairs
+            tape.take_given(this.pair_count as usize)
+        },
+    }
+}
+
+/// A kerning subtable in format 2.
+#[derive(Clone, Debug)]
+pub struct Format2 {
+    pub row_width: u16,
+    pub left_offset: u16,
+    pub right_offset: u16,
+    pub array_offset: u16,
+    pub left_classes: ClassTable,
+    pub right_classes: ClassTable,
+    pub values: Vec<i16>,
+}
+
+table! {
+    /// A kerning subtable in format 3.
+    pub Format3 {
+        glyph_count       (u16), // glyphCount
+        value_count       (u8 ), // kernValueCount
+        left_class_count  (u8 ), // leftClassCount
+        right_class_count (u8 ), // rightClassCount
+        flags             (u8 ), // flags
+
+        values (Vec<i16>) |this, tape| { // kernValue
+            tape.take_given(this.value_count as usize)
+        },
+
+        left_classes (Vec<u8>) |this, tape| { // leftClass
+            tape.take_given(this.glyph_count as usize)
+        },
+
+        right_classes (Vec<u8>) |this, tape| { // rightClass
+            tape.take_given(this.glyph_count as usize)
+        },
+
+        indices (Vec<u8>) |this, tape| { // kernIndex
+            tape.take_given(this.left_class_count as usize * this.right_class_count as usize)
+        },
+    }
+}
+
+table! {
+    /// A kerning pair.
+    #[derive(Copy)]
+    pub Pair {
+        left  (GlyphID), // left
+        right (GlyphID), // right
+        value (i16    ), // value
+    }
+}
+
+table! {
+    /// A class table of a subtable in format 2.
+    pub ClassTable {
+        first_glyph_id (GlyphID), // firstGlyph
+        glyph_count    (u16    ), // nGlyphs
+
+        offsets (Vec<u16>) |this, tape| { // offsets
+            tape.take_given(this.glyph_count as usize)
+        },
+    }
+}
+
+impl Kerning {
+    /// Return the kerning of a pair of glyphs.
+    ///
+    /// Only horizontal subtables that are not cross-stream are considered, and
+    /// their values are summed up unless a subtable overrides the sum.
+    pub fn pair(&self, left: GlyphID, right: GlyphID) -> i16 {
+        let mut value = 0i16;
+        match self {
+            Kerning::Version0(table) => {
+                for table in table.tables.iter() {
+                    let coverage = table.header.coverage;
+                    if !coverage.is_horizontal()
+                        || coverage.has_minimum_values()
+                        || coverage.is_cross_stream()
+                    {
+                        continue;
+                    }
+                    if let Some(other) = table.format.get(left, right) {
+                        value = if coverage.is_override() {
+                            other
+                        } else {
+                            value.saturating_add(other)
+                        };
+                    }
+                }
+            }
+            Kerning::Version1(table) => {
+                for table in table.tables.iter() {
+                    let coverage = table.header.coverage;
+                    if coverage.is_vertical()
+                        || coverage.is_cross_stream()
+                        || coverage.has_variations()
+                    {
+                        continue;
+                    }
+                    if let Some(other) = table.format.get(left, right) {
+                        value = value.saturating_add(other);
+                    }
+                }
+            }
+        }
+        value
+    }
+}
+
+impl Coverage0 {
+    /// Return the format of the subtable.
+    #[inline]
+    pub fn format(&self) -> u8 {
+        (self.0 >> 8) as u8
+    }
+}
+
+impl Coverage1 {
+    /// Return the format of the subtable.
+    #[inline]
+    pub fn format(&self) -> u8 {
+        self.0 as u8
+    }
+}
+
+impl Format {
+    /// Return the kerning of a pair of glyphs if present.
+    pub fn get(&self, left: GlyphID, right: GlyphID) -> Option<i16> {
+        match self {
+            Format::Format0(table) => table.get(left, right),
+            Format::Format2(table) => table.get(left, right),
+            Format::Format3(table) => table.get(left, right),
+            Format::Unknown(_) => None,
+        }
+    }
+}
+
+impl Format0 {
+    /// Return the kerning of a pair of glyphs if present.
+    pub fn get(&self, left: GlyphID, right: GlyphID) -> Option<i16> {
+        let key = (left, right);
+        self.pairs
+            .binary_search_by_key(&key, |pair| (pair.left, pair.right))
+            .ok()
+            .map(|i| self.pairs[i].value)
+    }
+}
+
+impl Format2 {
+    /// Return the kerning of a pair of glyphs if present.
+    pub fn get(&self, left: GlyphID, right: GlyphID) -> Option<i16> {
+        // The class values are byte offsets from the beginning of the subtable.
+        let offset =
+            self.left_classes.get(left)? as usize + self.right_classes.get(right)? as usize;
+        let index = offset.checked_sub(self.array_offset as usize)? / 2;
+        self.values.get(index).copied()
+    }
+}
+
+impl Format3 {
+    /// Return the kerning of a pair of glyphs if present.
+    pub fn get(&self, left: GlyphID, right: GlyphID) -> Option<i16> {
+        let left = *self.left_classes.get(left as usize)? as usize;
+        let right = *self.right_classes.get(right as usize)? as usize;
+        if left >= self.left_class_count as usize || right >= self.right_class_count as usize {
+            return None;
+        }
+        let index = *self
+            .indices
+            .get(left * self.right_class_count as usize + right)?;
+        self.values.get(index as usize).copied()
+    }
+}
+
+impl ClassTable {
+    /// Return the class value of a glyph if present.
+    pub fn get(&self, glyph_id: GlyphID) -> Option<u16> {
+        let index = glyph_id.checked_sub(self.first_glyph_id)?;
+        self.offsets.get(index as usize).copied()
+    }
+}
+
+impl crate::value::Read for Kerning {
+    fn read<T: crate::tape::Read>(tape: &mut T) -> Result<Self> {
+        Ok(match tape.peek::<u16>()? {
+            0 => Kerning::Version0(tape.take()?),
+            1 => Kerning::Version1(tape.take()?),
+            _ => raise!("found an unknown version of the kerning table"),
+        })
+    }
+}
+
+impl crate::value::Read for Subtable0 {
+    fn read<T: crate::tape::Read>(tape: &mut T) -> Result<Self> {
+        let position = tape.position()?;
+        let header = tape.take::<Header0>()?;
+        // The size is not reliable in format 0, as it overflows for large
+        // numbers of pairs.
+        let format = tape.take_given((position, header.coverage.format(), header.size as u64))?;
+        Ok(Self { header, format })
+    }
+}
+
+impl crate::value::Read for Subtable1 {
+    fn read<T: crate::tape::Read>(tape: &mut T) -> Result<Self> {
+        let position = tape.position()?;
+        let header = tape.take::<Header1>()?;
+        let format = tape.take_given((position, header.coverage.format(), header.size as u64))?;
+        Ok(Self { header, format })
+    }
+}
+
+impl crate::walue::Read<'static> for Format {
+    type Parameter = (u64, u8, u64);
+
+    fn read<T: crate::tape::Read>(
+        tape: &mut T,
+        (position, format, size): Self::Parameter,
+    ) -> Result<Self> {
+        let value = match format {
+            0 => return Ok(Format::Format0(tape.take()?)),
+            2 => Format::Format2(tape.take_given((position, size))?),
+            3 => Format::Format3(tape.take()?),
+            format => Format::Unknown(format),
+        };
+        tape.jump(position + size)?;
+        Ok(value)
+    }
+}
+
+impl crate::walue::Read<'static> for Format2 {
+    type Parameter = (u64, u64);
+
+    fn read<T: crate::tape::Read>(tape: &mut T, (position, size): Self::Parameter) -> Result<Self> {
+        let row_width = tape.take()?;
+        let left_offset = tape.take()?;
+        let right_offset = tape.take()?;
+        let array_offset = tape.take::<u16>()?;
+        let value_count = match size.checked_sub(array_offset as u64) {
+            Some(value) => value as usize / 2,
+            _ => raise!("found a malformed kerning subtable"),
+        };
+        tape.jump(position + left_offset as u64)?;
+        let left_classes = tape.take()?;
+        tape.jump(position + right_offset as u64)?;
+        let right_classes = tape.take()?;
+        tape.jump(position + array_offset as u64)?;
+        let values = tape.take_given(value_count)?;
+        Ok(Self {
+            row_width,
+            left_offset,
+            right_offset,
+            array_offset,
+            left_classes,
+            right_classes,
+            values,
+        })
+    }
+}
@@ -0,0 +1,79 @@
+//! The [linear threshold][1].
+//!
+//! [1]: https://learn.microsoft.com/en-us/typography/opentype/spec/ltsh
+
+use crate::tables::maximum_profile::MaximumProfile;
+use crate::{GlyphID, Result};
+
+table! {
+    @define
+    /// A linear threshold.
+    pub LinearThreshold {
+        version     (u16    ), // version
+        glyph_count (u16    ), // numGlyphs
+        thresholds  (Vec<u8>), // yPels
+    }
+}
+
+impl LinearThreshold {
+    /// Return the size in pixels per em from which a glyph scales linearly if
+    /// present.
+    #[inline]
+    pub fn get(&self, glyph_id: GlyphID) -> Option<u8> {
+        self.thresholds.get(glyph_id as usize).copied()
+    }
+
+    /// Check if a glyph scales linearly at a size in pixels per em.
+    pub fn is_linear(&self, glyph_id: GlyphID, ppem: u16) -> bool {
+        match self.get(glyph_id) {
+            Some(threshold) => threshold != 0 && ppem >= threshold as u16,
+            _ => false,
+        }
+    }
+}
+
+impl FromIterator<u8> for LinearThreshold {
+    /// Create an instance from the thresholds of all glyphs.
+    fn from_iter<T>(thresholds: T) -> Self
+    where
+        T: IntoIterator<Item = u8>,
+    {
+        let thresholds = thresholds.into_iter().collect::<Vec<_>>();
+        Self {
+            version: 0,
+            glyph_count: thresholds.len() as u16,
+            thresholds,
+        }
+    }
+}
+
+impl<'l> crate::walue::Read<'l> for LinearThreshold {
+    type Parameter = &'l MaximumProfile;
+
+    fn read<T: crate::tape::Read>(tape: &mut T, profile: Self::Parameter) -> Result<Self> {
+        let version = tape.take()?;
+        if version != 0 {
+            raise!("found an unknown version of the linear threshold");
+        }
+        let glyph_count = tape.take::<u16>()?;
+        if glyph_count as usize != profile.glyph_count() {
+            raise!("found a malformed linear threshold");
+        }
+        Ok(Self {
+            version,
+            glyph_count,
+            thresholds: tape.take_bytes(glyph_count as usize)?,
+        })
+    }
+}
+
+impl crate::value::Write for LinearThreshold {
+    fn write<T: crate::tape::Write>(&self, tape: &mut T) -> Result<()> {
+        if self.thresholds.len() > u16::MAX as usize {
+            raise!("found a malformed linear threshold");
+        }
+        tape.give(&self.version)?;
+        tape.give(&(self.thresholds.len() as u16))?;
+        tape.give_bytes(&self.thresholds)
+    }
+}
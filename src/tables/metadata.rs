@@ -0,0 +1,204 @@
+//! The [metadata table][1].
+//!
+//! [1]: https://learn.microsoft.com/en-us/typography/opentype/spec/meta
+
+use crate::{Result, Tag};
+
+const DESIGN_LANGUAGES: Tag = Tag(*b"dlng");
+const SUPPORTED_LANGUAGES: Tag = Tag(*b"slng");
+
+table! {
+    @position
+    /// A metadata table.
+    pub Metadata { // meta
+        version      (u32) = { 1 }, // version
+        flags        (u32), // flags
+        reserved     (u32), // reserved
+        record_count (u32), // dataMapsCount
+
+        records (Vec<Record>) |this, tape, _| { // dataMaps
+            tape.take_given(this.record_count as usize)
+        },
+
+        values (Vec<Value>) |this, tape, position| {
+            let mut values = Vec::with_capacity(this.records.len());
+            for record in this.records.iter() {
+                tape.jump(position + record.offset as u64)?;
+                values.push(tape.take_given((record.tag, record.size))?);
+            }
+            Ok(values)
+        },
+    }
+}
+
+table! {
+    @write
+    /// A data map.
+    #[derive(Copy)]
+    pub Record { // DataMap
+        tag    (Tag), // tag
+        offset (u32), // dataOffset
+        size   (u32), // dataLength
+    }
+}
+
+/// A value of a data map.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Value {
+    /// A list of ScriptLangTag values, as in `dlng` and `slng`.
+    ///
+    /// The values are written separated by commas, so they have to be nonempty
+    /// and may contain neither commas nor surrounding whitespace.
+    Languages(Vec<String>),
+    /// Raw data.
+    Data(Vec<u8>),
+}
+
+impl Metadata {
+    /// Return the value of a tag if present.
+    pub fn get(&self, tag: Tag) -> Option<&Value> {
+        self.records
+            .iter()
+            .position(|record| record.tag == tag)
+            .and_then(|index| self.values.get(index))
+    }
+
+    /// Return the languages the font is designed for if present.
+    #[inline]
+    pub fn design_languages(&self) -> Option<&[String]> {
+        self.languages(DESIGN_LANGUAGES)
+    }
+
+    /// Return the languages the font supports if present.
+    #[inline]
+    pub fn supported_languages(&self) -> Option<&[String]> {
+        self.languages(SUPPORTED_LANGUAGES)
+    }
+
+    fn languages(&self, tag: Tag) -> Option<&[String]> {
+        match self.get(tag)? {
+            Value::Languages(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+impl FromIterator<(Tag, Value)> for Metadata {
+    /// Create an instance from an iterator over tags and values.
+    ///
+    /// The records are laid out in the given order, and the offsets and sizes
+    /// are computed. If a value is malformed or the table does not fit in 32-bit
+    /// offsets, the offsets and sizes are left zero, and writing fails.
+    fn from_iter<T>(entries: T) -> Self
+    where
+        T: IntoIterator<Item = (Tag, Value)>,
+    {
+        let (tags, values): (Vec<_>, Vec<_>) = entries.into_iter().unzip();
+        let records = match layout(tags.iter().copied(), &values) {
+            Ok(records) => records,
+            _ => tags
+                .into_iter()
+                .map(|tag| Record {
+                    tag,
+                    offset: 0,
+                    size: 0,
+                })
+                .collect(),
+        };
+        Self {
+            version: 1,
+            flags: 0,
+            reserved: 0,
+            record_count: records.len() as u32,
+            records,
+            values,
+        }
+    }
+}
+
+impl Value {
+    fn encode(&self) -> Vec<u8> {
+        match self {
+            Value::Languages(values) => values.join(", ").into_bytes(),
+            Value::Data(data) => data.clone(),
+        }
+    }
+
+    fn size(&self) -> Result<usize> {
+        match self {
+            Value::Languages(values) => {
+                let malformed = |value: &String| {
+                    value.is_empty() || value.contains(',') || value.trim() != value
+                };
+                if values.iter().any(malformed) {
+                    raise!("found a malformed list of languages in the metadata table");
+                }
+                Ok(values.iter().map(String::len).sum::<usize>()
+                    + 2 * values.len().saturating_sub(1))
+            }
+            Value::Data(data) => Ok(data.len()),
+        }
+    }
+}
+
+impl crate::value::Write for Metadata {
+    fn write<T: crate::tape::Write>(&self, tape: &mut T) -> Result<()> {
+        if self.records.len() != self.values.len() {
+            raise!("found a malformed metadata table");
+        }
+        tape.give(&self.version)?;
+        tape.give(&self.flags)?;
+        tape.give(&self.reserved)?;
+        tape.give(&(self.records.len() as u32))?;
+        let records = layout(self.records.iter().map(|record| record.tag), &self.values)?;
+        tape.give(&records[..])?;
+        for value in self.values.iter() {
+            tape.give_bytes(&value.encode())?;
+        }
+        Ok(())
+    }
+}
+
+impl crate::walue::Read<'static> for Value {
+    type Parameter = (Tag, u32);
+
+    fn read<T: crate::tape::Read>(tape: &mut T, (tag, size): Self::Parameter) -> Result<Self> {
+        let data = tape.take_bytes(size as usize)?;
+        if tag != DESIGN_LANGUAGES && tag != SUPPORTED_LANGUAGES {
+            return Ok(Value::Data(data));
+        }
+        let value = match String::from_utf8(data) {
+            Ok(value) => value,
+            _ => raise!("found a malformed list of languages in the metadata table"),
+        };
+        Ok(Value::Languages(
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(String::from)
+                .collect(),
+        ))
+    }
+}
+
+fn layout<T>(tags: T, values: &[Value]) -> Result<Vec<Record>>
+where
+    T: IntoIterator<Item = Tag>,
+{
+    let mut records = Vec::with_capacity(values.len());
+    let mut offset = 16 + 12 * values.len();
+    for (tag, value) in tags.into_iter().zip(values.iter()) {
+        let size = value.size()?;
+        if offset + size > u32::MAX as usize {
+            raise!("found a malformed metadata table");
+        }
+        records.push(Record {
+            tag,
+            offset: offset as u32,
+            size: size as u32,
+        });
+        offset += size;
+    }
+    Ok(records)
+}
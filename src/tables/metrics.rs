@@ -0,0 +1,86 @@
+//! Computations shared by the horizontal and vertical metrics.
+
+use crate::GlyphID;
+
+/// Compute the minimum leading and trailing side bearings and the maximum
+/// extent along one axis.
+///
+/// The metrics give the advance and the leading side bearing of a glyph. The
+/// bounds give the minimum and maximum coordinates of a glyph along the same
+/// axis, or `None` for glyphs without contours, which are skipped. The extent
+/// is the leading side bearing plus the size of the bounds, and the trailing
+/// side bearing is what is left of the advance.
+pub fn summarize<T, F>(bounds: T, metrics: F) -> (i16, i16, i16)
+where
+    T: IntoIterator<Item = Option<(i16, i16)>>,
+    F: Fn(GlyphID) -> (u16, i16),
+{
+    let mut summary: Option<(i16, i16, i16)> = None;
+    for (glyph_id, bounds) in bounds.into_iter().enumerate() {
+        let Some((min, max)) = bounds else {
+            continue;
+        };
+        let (advance, leading) = metrics(glyph_id as GlyphID);
+        let extent = leading as i32 + (max as i32 - min as i32);
+        let current = (leading, clamp(advance as i32 - extent), clamp(extent));
+        summary = Some(match summary {
+            Some((leading, trailing, extent)) => (
+                leading.min(current.0),
+                trailing.min(current.1),
+                extent.max(current.2),
+            ),
+            _ => current,
+        });
+    }
+    summary.unwrap_or_default()
+}
+
+/// Split the advances and leading side bearings of all glyphs into long
+/// metrics and trailing side bearings.
+///
+/// The run of glyphs at the end sharing the advance of the last glyph keeps one
+/// long metric, and the rest of the run keeps only side bearings. The tables
+/// require at least one long metric, so a zero one is kept if there are no
+/// glyphs.
+pub fn compact<T>(metrics: T) -> (Vec<(u16, i16)>, Vec<i16>)
+where
+    T: IntoIterator<Item = (u16, i16)>,
+{
+    let mut longs = metrics.into_iter().collect::<Vec<_>>();
+    if longs.is_empty() {
+        longs.push((0, 0));
+    }
+    let mut count = longs.len();
+    while count > 1 && longs[count - 2].0 == longs[count - 1].0 {
+        count -= 1;
+    }
+    let bearings = longs.drain(count..).map(|(_, bearing)| bearing).collect();
+    (longs, bearings)
+}
+
+/// Return the advance and leading side bearing of a glyph.
+///
+/// Glyphs past the long metrics take the advance of the last long metric, and
+/// glyphs past the trailing side bearings also take the last side bearing.
+/// Values missing altogether are zero.
+pub fn get<R, F>(longs: &[R], bearings: &[i16], glyph_id: GlyphID, unpack: F) -> (u16, i16)
+where
+    F: Fn(&R) -> (u16, i16),
+{
+    let index = glyph_id as usize;
+    if let Some(record) = longs.get(index) {
+        return unpack(record);
+    }
+    let (advance, bearing) = longs.last().map(&unpack).unwrap_or_default();
+    let bearing = bearings
+        .get(index - longs.len())
+        .or(bearings.last())
+        .copied()
+        .unwrap_or(bearing);
+    (advance, bearing)
+}
+
+#[inline]
+fn clamp(value: i32) -> i16 {
+    value.clamp(i16::MIN as i32, i16::MAX as i32) as i16
+}
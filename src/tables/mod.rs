@@ -1,25 +1,53 @@
 //! Tables.
 
 pub mod character_mapping;
+pub mod control_value_program;
+pub mod control_values;
+pub mod digital_signature;
 pub mod font_header;
+pub mod font_program;
 pub mod glyph_data;
 pub mod glyph_mapping;
+pub mod grid_fitting;
+pub mod horizontal_device_metrics;
 pub mod horizontal_header;
 pub mod horizontal_metrics;
+pub mod kerning;
+pub mod linear_threshold;
 pub mod maximum_profile;
+pub mod metadata;
 pub mod names;
 pub mod offsets;
+pub mod pcl;
 pub mod postscript;
+pub mod vertical_device_metrics;
+pub mod vertical_header;
+pub mod vertical_metrics;
 pub mod windows_metrics;
 
+mod metrics;
+
 pub use character_mapping::CharacterMapping;
+pub use control_value_program::ControlValueProgram;
+pub use control_values::ControlValues;
+pub use digital_signature::DigitalSignature;
 pub use font_header::FontHeader;
+pub use font_program::FontProgram;
 pub use glyph_data::GlyphData;
 pub use glyph_mapping::GlyphMapping;
+pub use grid_fitting::GridFitting;
+pub use horizontal_device_metrics::HorizontalDeviceMetrics;
 pub use horizontal_header::HorizontalHeader;
 pub use horizontal_metrics::HorizontalMetrics;
+pub use kerning::Kerning;
+pub use linear_threshold::LinearThreshold;
 pub use maximum_profile::MaximumProfile;
+pub use metadata::Metadata;
 pub use names::Names;
 pub use offsets::Offsets;
+pub use pcl::PrinterCommandLanguage;
 pub use postscript::PostScript;
+pub use vertical_device_metrics::VerticalDeviceMetrics;
+pub use vertical_header::VerticalHeader;
+pub use vertical_metrics::VerticalMetrics;
 pub use windows_metrics::WindowsMetrics;
@@ -14,7 +14,7 @@ pub fn decode(data: &[u8], encoding_id: EncodingID) -> Option<String> {
 }
 
 pub fn decode_utf16(data: &[u8]) -> Option<String> {
-    if data.len() % 2 != 0 {
+    if !data.len().is_multiple_of(2) {
         return None;
     }
     let data = data
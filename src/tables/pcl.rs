@@ -0,0 +1,130 @@
+//! The [PCL 5 table][1].
+//!
+//! [1]: https://learn.microsoft.com/en-us/typography/opentype/spec/pclt
+
+use crate::{q32, Result};
+
+table! {
+    /// A PCL 5 table.
+    ///
+    /// The typeface, the character complement, and the file name occupy 16, 8,
+    /// and 6 bytes, respectively, and writing fails if their lengths differ.
+    pub PrinterCommandLanguage { // PCLT
+        version     (q32) = { q32(0x00010000) }, // version
+        font_number (u32), // fontNumber
+        pitch       (u16), // pitch
+        x_height    (u16), // xHeight
+        style       (u16), // style
+        type_family (u16), // typeFamily
+        cap_height  (u16), // capHeight
+        symbol_set  (u16), // symbolSet
+
+        typeface (Vec<u8>) |_, tape| { // typeface
+            tape.take_bytes(16)
+        },
+
+        character_complement (Vec<u8>) |_, tape| { // characterComplement
+            tape.take_bytes(8)
+        },
+
+        file_name (Vec<u8>) |_, tape| { // fileName
+            tape.take_bytes(6)
+        },
+
+        stroke_weight (StrokeWeight), // strokeWeight
+        width_type    (i8          ), // widthType
+        serif_bits    (u8          ), // serifStyle
+        reserved      (u8          ), // reserved
+    }
+}
+
+choices! {
+    /// A stroke weight.
+    pub StrokeWeight(i8) {
+        -7 => UltraThin,
+        -6 => ExtraThin,
+        -5 => Thin,
+        -4 => ExtraLight,
+        -3 => Light,
+        -2 => DemiLight,
+        -1 => SemiLight,
+        0 => Book,
+        1 => SemiBold,
+        2 => DemiBold,
+        3 => Bold,
+        4 => ExtraBold,
+        5 => Black,
+        6 => ExtraBlack,
+        7 => UltraBlack,
+        _ => Unknown,
+    }
+}
+
+choices! {
+    /// A serif style given by the lower six bits of the serif-style field.
+    pub SerifStyle(u8) {
+        0 => SansSerifSquare,
+        1 => SansSerifRound,
+        2 => SerifLine,
+        3 => SerifTriangle,
+        4 => SerifSwath,
+        5 => SerifBlock,
+        6 => SerifBracket,
+        7 => RoundedBracket,
+        8 => FlairSerif,
+        9 => ScriptNonconnecting,
+        10 => ScriptJoining,
+        11 => ScriptCalligraphic,
+        12 => ScriptBrokenLetter,
+        _ => Unknown,
+    }
+}
+
+choices! {
+    /// A serif class given by the upper two bits of the serif-style field.
+    pub SerifClass(u8) {
+        1 => SansSerif,
+        2 => Serif,
+        _ => Unknown,
+    }
+}
+
+impl PrinterCommandLanguage {
+    /// Return the serif style given by the lower six bits of `serif_bits`.
+    #[inline]
+    pub fn serif_style(&self) -> SerifStyle {
+        (self.serif_bits & 0b0011_1111).into()
+    }
+
+    /// Return the serif class given by the upper two bits of `serif_bits`.
+    #[inline]
+    pub fn serif_class(&self) -> SerifClass {
+        (self.serif_bits >> 6).into()
+    }
+}
+
+impl crate::value::Write for PrinterCommandLanguage {
+    fn write<T: crate::tape::Write>(&self, tape: &mut T) -> Result<()> {
+        if self.typeface.len() != 16
+            || self.character_complement.len() != 8
+            || self.file_name.len() != 6
+        {
+            raise!("found a malformed PCL 5 table");
+        }
+        tape.give(&self.version)?;
+        tape.give(&self.font_number)?;
+        tape.give(&self.pitch)?;
+        tape.give(&self.x_height)?;
+        tape.give(&self.style)?;
+        tape.give(&self.type_family)?;
+        tape.give(&self.cap_height)?;
+        tape.give(&self.symbol_set)?;
+        tape.give_bytes(&self.typeface)?;
+        tape.give_bytes(&self.character_complement)?;
+        tape.give_bytes(&self.file_name)?;
+        tape.give(&self.stroke_weight)?;
+        tape.give(&self.width_type)?;
+        tape.give(&self.serif_bits)?;
+        tape.give(&self.reserved)
+    }
+}
@@ -0,0 +1,170 @@
+//! The [vertical device metrics][1].
+//!
+//! [1]: https://learn.microsoft.com/en-us/typography/opentype/spec/vdmx
+
+use crate::Result;
+
+table! {
+    @define
+    /// Vertical device metrics.
+    ///
+    /// Each ratio range refers to a group via an offset, which is resolved to
+    /// the index of the group when reading. The groups are stored in the
+    /// ascending order of their offsets.
+    pub VerticalDeviceMetrics {
+        version     (u16       ), // version
+        group_count (u16       ), // numRecs
+        range_count (u16       ), // numRatios
+        ranges      (Vec<Range>), // ratRange
+        indices     (Vec<u16>  ), // offset
+        groups      (Vec<Group>), // groups
+    }
+}
+
+table! {
+    @write
+    /// A range of aspect ratios.
+    #[derive(Copy)]
+    pub Range { // RatioRange
+        character_set (u8), // bCharSet
+        x_ratio       (u8), // xRatio
+        start_y_ratio (u8), // yStartRatio
+        end_y_ratio   (u8), // yEndRatio
+    }
+}
+
+table! {
+    @write
+    /// A group of records of vertical device metrics.
+    pub Group { // VDMXGroup
+        record_count (u16), // recs
+        start_size   (u8 ), // startsz
+        end_size     (u8 ), // endsz
+
+        records (Vec<Record>) |this, tape| { // entry
+            tape.take_given(this.record_count as usize)
+        },
+    }
+}
+
+table! {
+    @write
+    /// A record of vertical device metrics.
+    #[derive(Copy)]
+    pub Record { // vTable
+        pixel_height (u16), // yPelHeight
+        max_y        (i16), // yMax
+        min_y        (i16), // yMin
+    }
+}
+
+impl VerticalDeviceMetrics {
+    /// Return the maximum and minimum vertical extents in pixels at a size in
+    /// pixels per em for an aspect ratio if present.
+    ///
+    /// The first ratio range matching the aspect ratio is used.
+    pub fn bounds(&self, ppem: u16, x_ratio: u16, y_ratio: u16) -> Option<(i16, i16)> {
+        let index = self
+            .ranges
+            .iter()
+            .position(|range| range.contains(x_ratio, y_ratio))?;
+        self.group(index)?
+            .get(ppem)
+            .map(|record| (record.max_y, record.min_y))
+    }
+
+    /// Return the group of a ratio range if present.
+    pub fn group(&self, index: usize) -> Option<&Group> {
+        self.groups.get(*self.indices.get(index)? as usize)
+    }
+}
+
+impl Range {
+    /// Check if an aspect ratio is within the range.
+    ///
+    /// A range with all ratios being zero matches any aspect ratio.
+    pub fn contains(&self, x_ratio: u16, y_ratio: u16) -> bool {
+        if self.x_ratio == 0 && self.start_y_ratio == 0 && self.end_y_ratio == 0 {
+            return true;
+        }
+        let value = y_ratio as u32 * self.x_ratio as u32;
+        value >= self.start_y_ratio as u32 * x_ratio as u32
+            && value <= self.end_y_ratio as u32 * x_ratio as u32
+    }
+}
+
+impl Group {
+    /// Return the record for a size in pixels per em if present.
+    pub fn get(&self, ppem: u16) -> Option<&Record> {
+        if ppem < self.start_size as u16 || ppem > self.end_size as u16 {
+            return None;
+        }
+        self.records
+            .binary_search_by_key(&ppem, |record| record.pixel_height)
+            .ok()
+            .map(|index| &self.records[index])
+    }
+}
+
+impl crate::value::Read for VerticalDeviceMetrics {
+    fn read<T: crate::tape::Read>(tape: &mut T) -> Result<Self> {
+        let position = tape.position()?;
+        let version = tape.take()?;
+        if version > 1 {
+            raise!("found an unknown version of the vertical device metrics");
+        }
+        let group_count = tape.take()?;
+        let range_count = tape.take::<u16>()?;
+        let ranges = tape.take_given(range_count as usize)?;
+        let offsets = tape.take_given::<Vec<u16>>(range_count as usize)?;
+        let mut unique = offsets.clone();
+        unique.sort();
+        unique.dedup();
+        let indices = offsets
+            .iter()
+            .map(|offset| unique.binary_search(offset).unwrap_or_default() as u16)
+            .collect();
+        let mut groups = Vec::with_capacity(unique.len());
+        for offset in unique {
+            tape.jump(position + offset as u64)?;
+            groups.push(tape.take()?);
+        }
+        Ok(Self {
+            version,
+            group_count,
+            range_count,
+            ranges,
+            indices,
+            groups,
+        })
+    }
+}
+
+impl crate::value::Write for VerticalDeviceMetrics {
+    fn write<T: crate::tape::Write>(&self, tape: &mut T) -> Result<()> {
+        let mut offsets = Vec::with_capacity(self.groups.len());
+        let mut offset = 6 + 6 * self.ranges.len();
+        for group in self.groups.iter() {
+            offsets.push(offset);
+            offset += 4 + 6 * group.records.len();
+        }
+        if self.indices.len() != self.ranges.len()
+            || offsets
+                .last()
+                .is_some_and(|&offset| offset > u16::MAX as usize)
+        {
+            raise!("found malformed vertical device metrics");
+        }
+        tape.give(&self.version)?;
+        tape.give(&(self.groups.len() as u16))?;
+        tape.give(&(self.ranges.len() as u16))?;
+        tape.give(&self.ranges[..])?;
+        for &index in self.indices.iter() {
+            match offsets.get(index as usize) {
+                Some(&offset) => tape.give(&(offset as u16))?,
+                _ => raise!("found malformed vertical device metrics"),
+            }
+        }
+        tape.give(&self.groups[..])
+    }
+}
@@ -0,0 +1,59 @@
+//! The [vertical header][1].
+//!
+//! [1]: https://learn.microsoft.com/en-us/typography/opentype/spec/vhea
+
+use crate::tables::metrics::summarize;
+use crate::tables::vertical_metrics::VerticalMetrics;
+
+table! {
+    @write
+    /// A vertical header.
+    #[derive(Copy)]
+    pub VerticalHeader {
+        major_version           (u16) = { 1 }, // majorVersion
+        minor_version           (u16), // minorVersion
+        ascender                (i16), // vertTypoAscender
+        descender               (i16), // vertTypoDescender
+        line_gap                (i16), // vertTypoLineGap
+        max_advance_height      (u16), // advanceHeightMax
+        min_top_side_bearing    (i16), // minTopSideBearing
+        min_bottom_side_bearing (i16), // minBottomSideBearing
+        max_y_extent            (i16), // yMaxExtent
+        caret_slope_rise        (i16), // caretSlopeRise
+        caret_slope_run         (i16), // caretSlopeRun
+        caret_offset            (i16), // caretOffset
+        reserved1               (i16), // reserved1
+        reserved2               (i16), // reserved2
+        reserved3               (i16), // reserved3
+        reserved4               (i16), // reserved4
+        metric_data_format      (i16), // metricDataFormat
+        vertical_metric_count   (u16), // numOfLongVerMetrics
+    }
+}
+
+impl VerticalHeader {
+    /// Update the fields derived from vertical metrics.
+    ///
+    /// The bounds are the minimum and maximum y coordinates of each glyph, or
+    /// `None` for glyphs without contours. The top side bearing runs from the
+    /// top of the vertical advance down to the maximum y coordinate, so
+    /// `max_y_extent` is the largest sum of the top side bearing and the glyph
+    /// height, and the bottom side bearing is the part of the advance height
+    /// left below the minimum y coordinate.
+    pub fn update<T>(&mut self, metrics: &VerticalMetrics, bounds: T)
+    where
+        T: IntoIterator<Item = Option<(i16, i16)>>,
+    {
+        self.vertical_metric_count = metrics.records.len() as _;
+        self.max_advance_height = metrics
+            .records
+            .iter()
+            .map(|record| record.advance_height)
+            .max()
+            .unwrap_or(0);
+        let (top, bottom, extent) = summarize(bounds, |glyph_id| metrics.get(glyph_id));
+        self.min_top_side_bearing = top;
+        self.min_bottom_side_bearing = bottom;
+        self.max_y_extent = extent;
+    }
+}
@@ -0,0 +1,99 @@
+//! The [vertical metrics][1].
+//!
+//! [1]: https://learn.microsoft.com/en-us/typography/opentype/spec/vmtx
+
+use crate::tables::maximum_profile::MaximumProfile;
+use crate::tables::metrics;
+use crate::tables::vertical_header::VerticalHeader;
+use crate::{GlyphID, Result};
+
+table! {
+    @define
+    /// Vertical metrics.
+    pub VerticalMetrics {
+        records           (Vec<Record>), // vMetrics
+        top_side_bearings (Vec<i16>   ), // topSideBearing
+    }
+}
+
+table! {
+    @write
+    /// A record of vertical metrics.
+    #[derive(Copy)]
+    pub Record { // longVerMetric
+        advance_height   (u16), // advanceHeight
+        top_side_bearing (i16), // topSideBearing
+    }
+}
+
+impl VerticalMetrics {
+    /// Return the advance height and top side bearing.
+    ///
+    /// Glyphs beyond the end of the table take the last advance height and the
+    /// last top side bearing.
+    pub fn get(&self, glyph_id: GlyphID) -> (u16, i16) {
+        metrics::get(&self.records, &self.top_side_bearings, glyph_id, |record| {
+            (record.advance_height, record.top_side_bearing)
+        })
+    }
+}
+
+impl FromIterator<(u16, i16)> for VerticalMetrics {
+    /// Create an instance from pairs of an advance height and a top side
+    /// bearing given for every glyph in order.
+    ///
+    /// Vertical advances are usually uniform, as in CJK fonts, so the run of
+    /// glyphs at the end with the same advance height as the last glyph keeps
+    /// one long record, and the rest of the run keeps only top side bearings.
+    /// A font without glyphs still gets one zero record, which `vhea` requires.
+    fn from_iter<T>(metrics: T) -> Self
+    where
+        T: IntoIterator<Item = (u16, i16)>,
+    {
+        let (records, top_side_bearings) = metrics::compact(metrics);
+        Self {
+            records: records
+                .into_iter()
+                .map(|(advance_height, top_side_bearing)| Record {
+                    advance_height,
+                    top_side_bearing,
+                })
+                .collect(),
+            top_side_bearings,
+        }
+    }
+}
+
+impl<'l> crate::walue::Read<'l> for VerticalMetrics {
+    type Parameter = (&'l VerticalHeader, &'l MaximumProfile);
+
+    fn read<T: crate::tape::Read>(
+        tape: &mut T,
+        (header, profile): Self::Parameter,
+    ) -> Result<Self> {
+        let metric_count = header.vertical_metric_count as usize;
+        let glyph_count = profile.glyph_count();
+        if metric_count == 0 || metric_count > glyph_count {
+            raise!("found a malformed vertical header");
+        }
+        let bearing_count = glyph_count - metric_count;
+        let mut table = VerticalMetrics {
+            records: Vec::with_capacity(metric_count),
+            top_side_bearings: Vec::with_capacity(bearing_count),
+        };
+        for _ in 0..metric_count {
+            table.records.push(tape.take()?);
+        }
+        for _ in 0..bearing_count {
+            table.top_side_bearings.push(tape.take()?);
+        }
+        Ok(table)
+    }
+}
+
+impl crate::value::Write for VerticalMetrics {
+    fn write<T: crate::tape::Write>(&self, tape: &mut T) -> Result<()> {
+        tape.give(&self.records[..])?;
+        tape.give(&self.top_side_bearings[..])
+    }
+}
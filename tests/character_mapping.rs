@@ -1,6 +1,9 @@
 #[macro_use]
 mod support;
 
+use std::io::Cursor;
+
+use truetype::tape::Write;
 use truetype::value::Read;
 
 use support::Fixture;
@@ -15,6 +18,66 @@ fn header() {
     assert!(table.table_count == 3);
 }
 
+// None of the fixtures contains a subtable in format 2, 8, 10, or 13, so the
+// subtables of these formats are built by hand.
+#[test]
+fn encoding_format2() {
+    use truetype::tables::character_mapping::{CharacterMapping, Encoding};
+
+    let mut data = vec![];
+    ok!(data.give(&[2u16, 6 + 2 * 256 + 2 * 8 + 2 * 5, 0][..]));
+    let mut keys = [0u16; 256];
+    keys[0x81] = 8;
+    ok!(data.give(&keys[..]));
+    ok!(data.give(&[0x41u16, 3, 0, 2 * 8 - 6][..]));
+    ok!(data.give(&[0x40u16, 2, 10, 2 * 3 + 8 - 6][..]));
+    ok!(data.give(&[1u16, 2, 3, 5, 6][..]));
+    let table = ok!(CharacterMapping::read(&mut wrap(3, 2, data)));
+    match &table.encodings[0] {
+        Encoding::Format2(table) => {
+            assert_eq!(table.sub_headers.len(), 2);
+            assert_eq!(table.glyph_ids.len(), 5);
+            assert_eq!(table.characters::<u32>(), [(0x41, 0x43), (0x8140, 0x8141)],);
+            assert_eq!(
+                table.mapping::<u32>(),
+                [(0x41, 1), (0x42, 2), (0x43, 3), (0x8140, 15), (0x8141, 16)].into(),
+            );
+            assert_eq!(table.lookup(0x42), Some(2));
+            assert_eq!(table.lookup(0x8141), Some(16));
+            assert_eq!(table.lookup(0x44), None);
+            assert_eq!(table.lookup(0x8142), None);
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn encoding_format2_last_lead_byte() {
+    use truetype::tables::character_mapping::{CharacterMapping, Encoding};
+
+    let mut data = vec![];
+    ok!(data.give(&[2u16, 6 + 2 * 256 + 2 * 8 + 2 * 3, 0][..]));
+    let mut keys = [0u16; 256];
+    keys[0xFF] = 8;
+    ok!(data.give(&keys[..]));
+    ok!(data.give(&[0x20u16, 1, 0, 2 * 8 - 6][..]));
+    ok!(data.give(&[0xFEu16, 2, 0, 2 + 8 - 6][..]));
+    ok!(data.give(&[10u16, 0, 21][..]));
+    let table = ok!(CharacterMapping::read(&mut wrap(3, 2, data)));
+    match &table.encodings[0] {
+        Encoding::Format2(table) => {
+            assert_eq!(table.characters::<u32>(), [(0x20, 0x20), (0xFFFE, 0xFFFF)]);
+            assert_eq!(
+                table.iter().collect::<Vec<_>>(),
+                [(0x20, 10), (0xFFFE, 0), (0xFFFF, 21)],
+            );
+            assert_eq!(table.lookup(0xFFFE), None);
+            assert_eq!(table.lookup(0xFFFF), Some(21));
+        }
+        _ => unreachable!(),
+    }
+}
+
 #[test]
 fn encoding_format4() {
     use truetype::tables::character_mapping::{CharacterMapping, Encoding};
@@ -23,7 +86,7 @@ fn encoding_format4() {
     let tables = &table.encodings;
     assert!(tables.len() == 3);
     match &tables[0] {
-        &Encoding::Format4(ref table) => {
+        Encoding::Format4(table) => {
             assert!(table.segment_count_x2 == 2 * 103);
             assert!(table.search_range == 2 * (1 << 103f64.log2().floor() as usize));
             assert!(table.end_codes.len() == 103);
@@ -36,7 +99,7 @@ fn encoding_format4() {
         _ => unreachable!(),
     }
     match &tables[2] {
-        &Encoding::Format4(ref table) => {
+        Encoding::Format4(table) => {
             assert!(table.segment_count_x2 == 2 * 103);
         }
         _ => unreachable!(),
@@ -51,7 +114,7 @@ fn encoding_format6() {
     let tables = &table.encodings;
     assert!(tables.len() == 3);
     match &tables[1] {
-        &Encoding::Format6(ref table) => {
+        Encoding::Format6(table) => {
             assert!(table.first_code == 9);
             assert!(table.entry_count == 247);
             assert!(table.glyph_ids.len() == 247);
@@ -60,6 +123,259 @@ fn encoding_format6() {
     }
 }
 
+#[test]
+fn encoding_format8() {
+    use truetype::tables::character_mapping::{CharacterMapping, Encoding};
+
+    let mut data = vec![];
+    ok!(data.give(&[8u16, 0][..]));
+    ok!(data.give(&[16u32 + 8192 + 4 + 12 * 2, 0][..]));
+    let mut is32 = [0u8; 8192];
+    is32[0] = 0b0100_0000;
+    ok!(data.give_bytes(&is32));
+    ok!(data.give(&[2u32, 0x10000, 0x10002, 7][..]));
+    ok!(data.give(&[0xFFFF_FFFEu32, 0xFFFF_FFFF, 20][..]));
+    let table = ok!(CharacterMapping::read(&mut wrap(3, 10, data)));
+    match &table.encodings[0] {
+        Encoding::Format8(table) => {
+            assert_eq!(table.is32.len(), 8192);
+            assert_eq!(
+                table.characters::<u32>(),
+                [(0x10000, 0x10002), (0xFFFF_FFFE, 0xFFFF_FFFF)],
+            );
+            assert_eq!(
+                table.mapping::<u32>(),
+                [
+                    (0x10000, 7),
+                    (0x10001, 8),
+                    (0x10002, 9),
+                    (0xFFFF_FFFE, 20),
+                    (0xFFFF_FFFF, 21),
+                ]
+                .into(),
+            );
+            assert_eq!(table.lookup(0xFFFF_FFFF), Some(21));
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn encoding_format10() {
+    use truetype::tables::character_mapping::{CharacterMapping, Encoding};
+
+    let mut data = vec![];
+    ok!(data.give(&[10u16, 0][..]));
+    ok!(data.give(&[20u32 + 2 * 3, 0, 0x20000, 3][..]));
+    ok!(data.give(&[4u16, 0, 5][..]));
+    let table = ok!(CharacterMapping::read(&mut wrap(3, 10, data)));
+    match &table.encodings[0] {
+        Encoding::Format10(table) => {
+            assert_eq!(table.characters::<u32>(), [(0x20000, 0x20002)]);
+            assert_eq!(
+                table.mapping::<u32>(),
+                [(0x20000, 4), (0x20001, 0), (0x20002, 5)].into(),
+            );
+        }
+        _ => unreachable!(),
+    }
+
+    let mut data = vec![];
+    ok!(data.give(&[10u16, 0][..]));
+    ok!(data.give(&[20u32 + 2 * 2, 0, 0xFFFF_FFFE, 2][..]));
+    ok!(data.give(&[4u16, 5][..]));
+    let table = ok!(CharacterMapping::read(&mut wrap(3, 10, data)));
+    match &table.encodings[0] {
+        Encoding::Format10(table) => {
+            assert_eq!(table.characters::<u32>(), [(0xFFFF_FFFE, 0xFFFF_FFFF)]);
+            assert_eq!(
+                table.iter().collect::<Vec<_>>(),
+                [(0xFFFF_FFFE, 4), (0xFFFF_FFFF, 5)],
+            );
+            assert_eq!(table.lookup(0xFFFF_FFFF), Some(5));
+        }
+        _ => unreachable!(),
+    }
+    assert_eq!(table.coverage().ranges(), [(0xFFFF_FFFE, 0xFFFF_FFFF)]);
+
+    let mut data = vec![];
+    ok!(data.give(&[10u16, 0][..]));
+    ok!(data.give(&[20u32 + 2 * 2, 0, 0xFFFF_FFFF, 2][..]));
+    ok!(data.give(&[4u16, 5][..]));
+    let table = ok!(CharacterMapping::read(&mut wrap(3, 10, data)));
+    match &table.encodings[0] {
+        Encoding::Format10(table) => {
+            assert_eq!(table.characters::<u32>(), [(0xFFFF_FFFF, 0xFFFF_FFFF)]);
+            assert_eq!(table.iter().collect::<Vec<_>>(), [(0xFFFF_FFFF, 4)]);
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn encoding_format12() {
+    use truetype::tables::character_mapping::{CharacterMapping, Encoding};
+
+    let table = ok!(CharacterMapping::read(&mut setup!(MPlus2P, "cmap")));
+    match &table.encodings[1] {
+        Encoding::Format12(table) => {
+            let characters = table.characters::<u32>();
+            assert_eq!(characters.len(), table.groups.len());
+            for code in table.mapping::<u32>().keys() {
+                assert!(characters
+                    .iter()
+                    .any(|&(start, end)| start <= *code && *code <= end));
+            }
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn encoding_format13() {
+    use truetype::tables::character_mapping::{CharacterMapping, Encoding};
+
+    let mut data = vec![];
+    ok!(data.give(&[13u16, 0][..]));
+    ok!(data.give(&[16u32 + 3 * 12, 0, 3, 0x100, 0x1FF, 9, 0x300, 0x300, 10][..]));
+    ok!(data.give(&[0xFFFF_FF00u32, 0xFFFF_FFFF, 11][..]));
+    let table = ok!(CharacterMapping::read(&mut wrap(0, 6, data)));
+    match &table.encodings[0] {
+        Encoding::Format13(table) => {
+            assert_eq!(
+                table.characters::<u32>(),
+                [(0x100, 0x1FF), (0x300, 0x300), (0xFFFF_FF00, 0xFFFF_FFFF)],
+            );
+            let mapping = table.mapping::<u32>();
+            assert_eq!(mapping.len(), 0x100 + 1 + 0x100);
+            assert_eq!(mapping[&0x100], 9);
+            assert_eq!(mapping[&0x1FF], 9);
+            assert_eq!(mapping[&0x300], 10);
+            assert_eq!(mapping[&0xFFFF_FFFF], 11);
+            assert_eq!(table.lookup(0xFFFF_FFFF), Some(11));
+            assert_eq!(table.lookup(0xFFFF_FEFF), None);
+        }
+        _ => unreachable!(),
+    }
+    assert!(table.coverage().ranges().is_empty());
+    assert!(table.reverse(false).characters(11).is_empty());
+}
+
+#[test]
+fn encoding_format14() {
+    use truetype::tables::character_mapping::{CharacterMapping, Encoding, Variant};
+
+    let table = ok!(CharacterMapping::read(&mut setup!(MPlus2P, "cmap")));
+    let fallback = &table.encodings[1];
+    let mapping = Fixture::MPlus2P.mappings()[1].clone();
+    match &table.encodings[2] {
+        Encoding::Format14(table) => {
+            assert_eq!(table.selectors.len(), 7);
+            assert_eq!(table.default_variations.len(), 7);
+            assert_eq!(table.non_default_variations.len(), 7);
+            assert!(table.default_variations[3].is_none());
+            assert_eq!(ok!(table.default_variations[0].as_ref()).ranges.len(), 2);
+            assert_eq!(
+                ok!(table.non_default_variations[6].as_ref()).mappings.len(),
+                1
+            );
+            assert_eq!(
+                table.glyph_for('\u{7259}', '\u{E0100}', fallback),
+                Some(Variant::Default(mapping[&0x7259])),
+            );
+            assert_eq!(
+                table.glyph_for('\u{53C9}', '\u{E0100}', fallback),
+                Some(Variant::NonDefault(0x218B)),
+            );
+            assert_eq!(table.glyph_for('A', '\u{E0100}', fallback), None);
+            assert_eq!(table.glyph_for('\u{53C9}', '\u{E0106}', fallback), None);
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn coverage() {
+    use std::collections::BTreeMap;
+
+    use truetype::tables::CharacterMapping;
+
+    let table = ok!(CharacterMapping::read(&mut setup!(MPlus2P, "cmap")));
+    let coverage = table.coverage();
+    assert!(coverage.supports("Hello, 世界"));
+    assert!(!coverage.supports("\u{10FFFF}"));
+    for pair in coverage.ranges().windows(2) {
+        assert!(pair[0].1 + 1 < pair[1].0);
+    }
+
+    let mapping = BTreeMap::from([(0x41, 1), (0x42, 2), (0x44, 0), (0x1F600, 3)]);
+    let table = ok!(CharacterMapping::from_mapping(&mapping, &BTreeMap::new()));
+    let coverage = table.coverage();
+    assert_eq!(coverage.ranges(), [(0x41, 0x42), (0x1F600, 0x1F600)]);
+    assert!(coverage.contains(0x42));
+    assert!(!coverage.contains(0x43));
+    assert!(!coverage.contains(0x44));
+    assert!(coverage.supports("AB\u{1F600}"));
+    assert!(!coverage.supports("ABC"));
+}
+
+#[test]
+fn coverage_format12() {
+    use truetype::tables::CharacterMapping;
+
+    let mut data = vec![];
+    ok!(data.give(&[12u16, 0][..]));
+    ok!(data.give(&[16u32 + 12 * 3, 0, 3][..]));
+    ok!(data.give(&[0x41u32, 0x42, 0][..]));
+    ok!(data.give(&[0x100u32, 0x1FF, 1][..]));
+    ok!(data.give(&[0xFFFF_FF00u32, 0xFFFF_FFFF, 7][..]));
+    let table = ok!(CharacterMapping::read(&mut wrap(3, 10, data)));
+    let coverage = table.coverage();
+    assert_eq!(
+        coverage.ranges(),
+        [(0x42, 0x42), (0x100, 0x1FF), (0xFFFF_FF00, 0xFFFF_FFFF)],
+    );
+    assert!(!coverage.contains(0x41));
+    assert!(coverage.contains(0xFFFF_FFFF));
+
+    let mut data = vec![];
+    ok!(data.give(&[12u16, 0][..]));
+    ok!(data.give(&[16u32 + 12 * 3, 0, 3][..]));
+    ok!(data.give(&[0u32, 0xFFFF_FFFF, 1][..]));
+    ok!(data.give(&[0xFFFFu32, 0xFFFF, 2][..]));
+    ok!(data.give(&[0xFFFF_FFFFu32, 0xFFFF_FFFF, 3][..]));
+    let table = ok!(CharacterMapping::read(&mut wrap(3, 10, data)));
+    assert_eq!(
+        table.coverage().ranges(),
+        [(0, 0xFFFF), (0xFFFF_FFFF, 0xFFFF_FFFF)],
+    );
+}
+
+#[test]
+fn encoding_format12_large_glyph_ids() {
+    use truetype::tables::character_mapping::{CharacterMapping, Encoding};
+
+    let mut data = vec![];
+    ok!(data.give(&[12u16, 0][..]));
+    ok!(data.give(&[16u32 + 12 * 2, 0, 2][..]));
+    ok!(data.give(&[0x41u32, 0x43, 0xFFFE][..]));
+    ok!(data.give(&[0x100u32, 0x101, 0xFFFF_FFFF][..]));
+    let table = ok!(CharacterMapping::read(&mut wrap(3, 10, data)));
+    match &table.encodings[0] {
+        Encoding::Format12(table) => {
+            assert_eq!(table.lookup(0x42), Some(0xFFFF));
+            assert_eq!(table.lookup(0x43), None);
+            assert_eq!(table.lookup(0x100), None);
+            assert_eq!(
+                table.iter().collect::<Vec<_>>(),
+                [(0x41, 0xFFFE), (0x42, 0xFFFF)],
+            );
+        }
+        _ => unreachable!(),
+    }
+    assert_eq!(table.coverage().ranges(), [(0x41, 0x42)]);
+}
+
 #[test]
 fn encoding_formats() {
     use truetype::tables::character_mapping::{CharacterMapping, Encoding};
@@ -79,11 +395,11 @@ fn encoding_formats() {
         assert!(table.encodings.len() == expected_mappings.len());
         for (encoding, expected_mapping) in table.encodings.iter().zip(expected_mappings) {
             let mut mapping = match encoding {
-                &Encoding::Format0(ref encoding) => encoding.mapping::<u32>(),
-                &Encoding::Format4(ref encoding) => encoding.mapping::<u32>(),
-                &Encoding::Format6(ref encoding) => encoding.mapping::<u32>(),
-                &Encoding::Format12(ref encoding) => encoding.mapping::<u32>(),
-                &Encoding::Format14(_) => continue,
+                Encoding::Format0(encoding) => encoding.mapping::<u32>(),
+                Encoding::Format4(encoding) => encoding.mapping::<u32>(),
+                Encoding::Format6(encoding) => encoding.mapping::<u32>(),
+                Encoding::Format12(encoding) => encoding.mapping::<u32>(),
+                Encoding::Format14(_) => continue,
                 _ => unreachable!(),
             };
             mapping.retain(|_, value| value != &0);
@@ -92,6 +408,236 @@ fn encoding_formats() {
     }
 }
 
+#[test]
+fn from_mapping() {
+    use std::collections::{BTreeMap, HashMap};
+
+    use truetype::tables::character_mapping::{CharacterMapping, Encoding, Variant};
+
+    let mapping = Fixture::OpenSans.mappings()[0]
+        .iter()
+        .map(|(&code, &glyph_id)| (code, glyph_id))
+        .collect::<BTreeMap<_, _>>();
+    let table = ok!(CharacterMapping::from_mapping(&mapping, &BTreeMap::new()));
+    let mut data = vec![];
+    ok!(data.give(&table));
+    let table = ok!(CharacterMapping::read(&mut Cursor::new(data)));
+    assert_eq!(table.records.len(), 2);
+    assert_eq!(table.records[0].offset, table.records[1].offset);
+    match &table.encodings[0] {
+        Encoding::Format4(encoding) => {
+            let expected_mapping = mapping.clone().into_iter().collect::<HashMap<_, _>>();
+            assert!(encoding.mapping::<u32>() == expected_mapping);
+            assert!(encoding.glyph_ids.len() < mapping.len());
+        }
+        _ => unreachable!(),
+    }
+
+    let mapping = [(0x41, 1), (0x42, 2), (0x43, 3), (0x1F600, 4), (0x1F601, 5)].into();
+    let sequences = [
+        ((0x41, 0xFE00), None),
+        ((0x42, 0xFE00), None),
+        ((0x43, 0xFE00), Some(6)),
+        ((0x1F600, 0xFE0F), Some(7)),
+    ]
+    .into();
+    let table = ok!(CharacterMapping::from_mapping(&mapping, &sequences));
+    let mut data = vec![];
+    ok!(data.give(&table));
+    let table = ok!(CharacterMapping::read(&mut Cursor::new(data)));
+    let records = table
+        .records
+        .iter()
+        .map(|record| (record.platform_id, record.encoding_id))
+        .collect::<Vec<_>>();
+    assert_eq!(records, [(0, 3), (0, 4), (0, 5), (3, 1), (3, 10)]);
+    match &table.encodings[0] {
+        Encoding::Format4(encoding) => {
+            assert_eq!(encoding.start_codes, [0x41, 0xFFFF]);
+            assert_eq!(encoding.id_deltas, [-0x40, 1]);
+            assert!(encoding.glyph_ids.is_empty());
+        }
+        _ => unreachable!(),
+    }
+    match &table.encodings[1] {
+        Encoding::Format12(encoding) => {
+            assert_eq!(encoding.groups.len(), 2);
+            assert_eq!(encoding.lookup(0x1F601), Some(5));
+        }
+        _ => unreachable!(),
+    }
+    match &table.encodings[2] {
+        Encoding::Format14(encoding) => {
+            let fallback = &table.encodings[1];
+            assert_eq!(encoding.selectors.len(), 2);
+            assert_eq!(
+                encoding.glyph_for('B', '\u{FE00}', fallback),
+                Some(Variant::Default(2)),
+            );
+            assert_eq!(
+                encoding.glyph_for('C', '\u{FE00}', fallback),
+                Some(Variant::NonDefault(6)),
+            );
+            assert_eq!(
+                encoding.glyph_for('\u{1F600}', '\u{FE0F}', fallback),
+                Some(Variant::NonDefault(7)),
+            );
+            assert_eq!(encoding.glyph_for('D', '\u{FE00}', fallback), None);
+        }
+        _ => unreachable!(),
+    }
+    assert_eq!(table.lookup(0x1F600), Some(4));
+}
+
+#[test]
+fn reverse() {
+    use std::collections::BTreeMap;
+
+    use truetype::tables::CharacterMapping;
+
+    let table = ok!(CharacterMapping::read(&mut setup!(OpenSans, "cmap")));
+    let index = table.reverse(false);
+    for (code, glyph_id) in Fixture::OpenSans.mappings()[0].iter() {
+        assert!(index.characters(*glyph_id).binary_search(code).is_ok());
+    }
+    assert!(index
+        .iter()
+        .all(|(_, codes)| codes.windows(2).all(|pair| pair[0] < pair[1])));
+
+    let table = ok!(CharacterMapping::read(&mut setup!(MPlus2P, "cmap")));
+    let index = table.reverse(true);
+    assert!(index.sequences(0x218B).contains(&(0x53C9, 0xE0100)));
+    assert!(table.reverse(false).sequences(0x218B).is_empty());
+
+    let mapping = BTreeMap::from([(0x41, 1), (0x42, 2), (0x61, 1), (0x1F600, 1)]);
+    let sequences = [((0x41, 0xFE00), None), ((0x42, 0xFE00), Some(6))].into();
+    let table = ok!(CharacterMapping::from_mapping(&mapping, &sequences));
+    let index = table.reverse(true);
+    assert_eq!(index.characters(1), [0x41, 0x61, 0x1F600]);
+    assert_eq!(index.characters(6), []);
+    assert_eq!(index.sequences(1), [(0x41, 0xFE00)]);
+    assert_eq!(index.sequences(6), [(0x42, 0xFE00)]);
+}
+
+#[test]
+fn view() {
+    use std::collections::BTreeMap;
+
+    use truetype::tables::CharacterMapping;
+
+    let table = ok!(CharacterMapping::read(&mut setup!(MPlus2P, "cmap")));
+    let view = ok!(table.view());
+    assert_eq!((view.platform_id, view.encoding_id), (3, 10));
+    assert!(!view.is_symbol());
+
+    let table = ok!(CharacterMapping::read(&mut setup!(SourceSerif, "cmap")));
+    let view = ok!(table.view());
+    assert_eq!((view.platform_id, view.encoding_id), (3, 1));
+    assert!(view.iter().eq(table.encodings[2].iter()));
+
+    let mapping = BTreeMap::from([(0xF041, 5), (0xF042, 6)]);
+    let table = ok!(CharacterMapping::from_mapping(&mapping, &BTreeMap::new()));
+    let mut data = vec![];
+    ok!(data.give(&table.encodings[0]));
+    let table = ok!(CharacterMapping::read(&mut wrap(3, 0, data)));
+    let view = ok!(table.view());
+    assert!(view.is_symbol());
+    assert_eq!(view.lookup(0x41), Some(5));
+    assert_eq!(view.lookup(0xF042), Some(6));
+    assert_eq!(view.lookup(0x43), None);
+    assert_eq!(view.iter().collect::<Vec<_>>(), [(0x41, 5), (0x42, 6)]);
+    assert_eq!(table.lookup(0x42), Some(6));
+}
+
+#[test]
+fn write() {
+    use truetype::tables::character_mapping::{CharacterMapping, Encoding, Variant};
+
+    let table = ok!(CharacterMapping::read(&mut setup!(MPlus2P, "cmap")));
+    let mut data = vec![];
+    ok!(data.give(&table));
+    let other = ok!(CharacterMapping::read(&mut Cursor::new(data)));
+    assert_eq!(other.records.len(), table.records.len());
+    for (encoding, other) in table.encodings.iter().zip(other.encodings.iter()) {
+        assert!(encoding.iter().eq(other.iter()));
+    }
+    match &other.encodings[2] {
+        Encoding::Format14(encoding) => {
+            let fallback = &other.encodings[1];
+            assert_eq!(
+                encoding.glyph_for('\u{53C9}', '\u{E0100}', fallback),
+                Some(Variant::NonDefault(0x218B)),
+            );
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn lookup() {
+    use std::collections::HashMap;
+
+    use truetype::tables::character_mapping::{CharacterMapping, Encoding};
+
+    let fixtures = &[
+        Fixture::MPlus2P,
+        Fixture::OpenSans,
+        Fixture::SourceSerif,
+        Fixture::VeraMono,
+    ];
+    for fixture in fixtures {
+        let table = ok!(CharacterMapping::read(&mut support::setup(
+            *fixture,
+            Some("cmap"),
+        )));
+        for (encoding, expected_mapping) in table.encodings.iter().zip(fixture.mappings()) {
+            if let Encoding::Format14(_) = encoding {
+                continue;
+            }
+            let mut mapping = encoding.iter().collect::<HashMap<_, _>>();
+            mapping.retain(|_, value| value != &0);
+            assert!(mapping == expected_mapping);
+            for (code, glyph_id) in expected_mapping {
+                assert_eq!(encoding.lookup(code), Some(glyph_id));
+            }
+            for (code, _) in encoding.iter().filter(|&(_, glyph_id)| glyph_id == 0) {
+                assert_eq!(encoding.lookup(code), None);
+            }
+            assert_eq!(encoding.lookup(0x10FFFF + 1), None);
+        }
+    }
+
+    let table = ok!(CharacterMapping::read(&mut setup!(OpenSans, "cmap")));
+    let expected_mapping = &Fixture::OpenSans.mappings()[0];
+    assert_eq!(
+        table.lookup('A' as u32),
+        expected_mapping.get(&('A' as u32)).copied()
+    );
+    assert_eq!(table.lookup(0x10FFFF), None);
+}
+
+#[test]
+fn lookup_missing() {
+    use truetype::tables::character_mapping::{CharacterMapping, Encoding};
+
+    let mut data = vec![];
+    ok!(data.give(&[4u16, 14 + 2 * 2 * 4 + 2 + 2 * 3, 0, 2 * 2, 4, 1, 0][..]));
+    ok!(data.give(&[0x43u16, 0xFFFF, 0, 0x41, 0xFFFF, 0, 1, 2 * 2, 0][..]));
+    ok!(data.give(&[5u16, 0, 7][..]));
+    let table = ok!(CharacterMapping::read(&mut wrap(3, 1, data)));
+    match &table.encodings[0] {
+        Encoding::Format4(encoding) => {
+            assert_eq!(encoding.characters::<u32>(), [(0x41, 0x43)]);
+            assert_eq!(encoding.lookup(0x41), Some(5));
+            assert_eq!(encoding.lookup(0x42), None);
+            assert_eq!(encoding.lookup(0x43), Some(7));
+        }
+        _ => unreachable!(),
+    }
+    assert_eq!(table.lookup(0x42), None);
+    assert!(!table.coverage().contains(0x42));
+}
+
 #[test]
 fn records() {
     use truetype::tables::CharacterMapping;
@@ -106,3 +652,12 @@ fn records() {
     assert!(tables[2].platform_id == 3);
     assert!(tables[2].encoding_id == 1);
 }
+
+fn wrap(platform_id: u16, encoding_id: u16, data: Vec<u8>) -> Cursor<Vec<u8>> {
+    let mut cursor = Cursor::new(vec![]);
+    ok!(cursor.give(&[0u16, 1, platform_id, encoding_id][..]));
+    ok!(cursor.give(&12u32));
+    ok!(cursor.give_bytes(&data));
+    cursor.set_position(0);
+    cursor
+}
@@ -0,0 +1,33 @@
+#[macro_use]
+mod support;
+
+use truetype::tables::ControlValueProgram;
+use truetype::tape::Write;
+use truetype::walue::Read;
+use truetype::Font;
+
+#[test]
+fn read() {
+    let table = ok!(ControlValueProgram::read(
+        &mut setup!(OpenSans, "prep"),
+        265
+    ));
+    assert_eq!(table.len(), 265);
+    assert_eq!(table[..3], [0x40, 0x99, 0x09]);
+    assert_eq!(table[264], 94);
+
+    let mut font = ok!(Font::read(setup!(OpenSans)));
+    assert_eq!(ok!(font.control_value_program()), &table);
+    assert_eq!(ok!(font.control_values()).len(), 80);
+}
+
+#[test]
+fn write() {
+    let table = ok!(ControlValueProgram::read(
+        &mut setup!(OpenSans, "prep"),
+        265
+    ));
+    let mut data = vec![];
+    ok!(data.give(&table));
+    assert_eq!(data, table.0);
+}
@@ -0,0 +1,26 @@
+#[macro_use]
+mod support;
+
+use std::io::Cursor;
+
+use truetype::tables::ControlValues;
+use truetype::tape::Write;
+use truetype::walue::Read;
+
+#[test]
+fn read() {
+    let table = ok!(ControlValues::read(&mut setup!(OpenSans, "cvt "), 160));
+    assert_eq!(table.len(), 80);
+    assert_eq!(table[..4], [1556, 23, 78, 1462]);
+    assert!(ControlValues::read(&mut setup!(OpenSans, "cvt "), 159).is_err());
+}
+
+#[test]
+fn write() {
+    let table = ok!(ControlValues::read(&mut setup!(OpenSans, "cvt "), 160));
+    let mut data = vec![];
+    ok!(data.give(&table));
+    assert_eq!(data.len(), 160);
+    let other = ok!(ControlValues::read(&mut Cursor::new(data), 160));
+    assert_eq!(other, table);
+}
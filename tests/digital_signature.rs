@@ -0,0 +1,63 @@
+#[macro_use]
+mod support;
+
+use truetype::tables::digital_signature::Signature;
+use truetype::tables::DigitalSignature;
+use truetype::tape::Write;
+use truetype::value::Read;
+
+#[test]
+fn read() {
+    let table = ok!(DigitalSignature::read(&mut setup!(OpenSans, "DSIG")));
+    assert_eq!(table.version, 1);
+    assert_eq!(table.signature_count, 1);
+    assert!(table.flags.cannot_be_resigned());
+    assert_eq!(table.records[0].format, 1);
+    assert_eq!(table.records[0].size, 5470);
+    assert_eq!(table.records[0].offset, 20);
+    match &table.signatures[0] {
+        Signature::Format1(signature) => {
+            assert_eq!(signature.size, 5462);
+            assert_eq!(signature.data.len(), 5462);
+            assert_eq!(signature.data[..2], [0x30, 0x82]);
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn write() {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let table = ok!(DigitalSignature::read(&mut setup!(OpenSans, "DSIG")));
+    let mut data = vec![];
+    ok!(data.give(&table));
+    assert_eq!(data.len(), 5490);
+    let mut file = setup!(OpenSans);
+    let mut other = vec![0; 5490];
+    ok!(file.seek(SeekFrom::Start(207404)));
+    ok!(file.read_exact(&mut other));
+    assert_eq!(data, other);
+}
+
+#[test]
+fn stub() {
+    use truetype::{Font, FontBuilder, Tag};
+
+    let table = ok!(DigitalSignature::read(&mut setup!(ZenLoop, "DSIG")));
+    assert_eq!((table.version, table.signature_count), (1, 0));
+    assert!(table.signatures.is_empty());
+
+    let mut data = vec![];
+    ok!(data.give(&DigitalSignature::stub()));
+    assert_eq!(data, [0, 0, 0, 1, 0, 0, 0, 0]);
+
+    let mut font = ok!(Font::read(setup!(ZenLoop)));
+    let mut builder = ok!(FontBuilder::new(Tag([0, 1, 0, 0])));
+    for tag in [*b"head", *b"maxp"] {
+        builder.add(Tag(tag), ok!(font.take_bytes(Tag(tag))));
+    }
+    ok!(builder.add_table(Tag(*b"DSIG"), &DigitalSignature::stub()));
+    let mut font = ok!(Font::read(std::io::Cursor::new(ok!(builder.build()))));
+    assert_eq!(ok!(font.take_bytes(Tag(*b"DSIG"))), data);
+}
@@ -0,0 +1,98 @@
+#[macro_use]
+mod support;
+
+mod open_sans {
+    use std::io::Cursor;
+
+    use truetype::tables::glyph_data::Description;
+    use truetype::tables::FontHeader;
+    use truetype::{Font, FontBuilder, Tag};
+
+    #[test]
+    fn build() {
+        let mut font = ok!(Font::read(setup!(OpenSans)));
+        let mut builder = ok!(FontBuilder::new(Tag([0, 1, 0, 0])));
+        let tags = font
+            .offsets
+            .records
+            .iter()
+            .rev()
+            .map(|record| record.tag)
+            .collect::<Vec<_>>();
+        for tag in tags.iter() {
+            builder.add(*tag, ok!(font.take_bytes(*tag)));
+        }
+        let data = ok!(builder.build());
+        assert_eq!(data.len() % 4, 0);
+        assert_eq!(ok!(FontHeader::checksum(&mut Cursor::new(&data))), 0);
+
+        let mut other = ok!(Font::read(Cursor::new(&data)));
+        let header = &other.offsets.header;
+        assert_eq!(header.table_count, 19);
+        assert_eq!(header.search_range, 16 * 16);
+        assert_eq!(header.entry_selector, 4);
+        assert_eq!(header.range_shift, 19 * 16 - 16 * 16);
+        assert_eq!(header.search_range, font.offsets.header.search_range);
+        let records = other.offsets.records.clone();
+        assert!(records.windows(2).all(|pair| pair[0].tag < pair[1].tag));
+        let mut tape = Cursor::new(&data);
+        for record in records.iter() {
+            assert_eq!(record.offset % 4, 0);
+            assert_eq!(record.checksum, ok!(record.checksum(&mut tape)));
+            if record.tag != b"head" {
+                assert_eq!(
+                    ok!(other.take_bytes(record.tag)),
+                    ok!(font.take_bytes(record.tag))
+                );
+            }
+        }
+        assert_eq!(ok!(other.font_header()).units_per_em, 2048);
+        assert_eq!(ok!(other.glyph_data()).len(), 938);
+    }
+
+    #[test]
+    fn glyph_data() {
+        let mut font = ok!(Font::read(setup!(OpenSans)));
+        let table = ok!(font.glyph_data());
+        let glyph = ok!(table[0].as_ref());
+        assert_eq!((glyph.min_x, glyph.max_x), (193, 1034));
+        assert_eq!((glyph.min_y, glyph.max_y), (0, 1462));
+        match glyph.description {
+            Description::Simple(ref description) => {
+                assert_eq!(&description.x, &[193, 841, 0, -841, 104, 633, 0, -633]);
+            }
+            _ => unreachable!(),
+        }
+        let glyph_count = table.len();
+        assert_eq!(ok!(font.maximum_profile()).glyph_count(), glyph_count);
+    }
+
+    #[test]
+    fn take_bytes() {
+        let mut font = ok!(Font::read(setup!(OpenSans)));
+        assert_eq!(ok!(font.take_bytes(Tag(*b"head"))).len(), 54);
+        assert!(font.contains(Tag(*b"glyf")));
+        assert!(!font.contains(Tag(*b"CFF ")));
+    }
+}
+
+mod source_serif {
+    use truetype::{Font, Tag};
+
+    #[test]
+    fn horizontal_metrics() {
+        let mut font = ok!(Font::read(setup!(SourceSerif)));
+        assert_eq!(ok!(font.font_header()).units_per_em, 1000);
+        assert_eq!(ok!(font.horizontal_metrics()).get(42), (549, 45));
+    }
+
+    #[test]
+    fn missing() {
+        let mut font = ok!(Font::read(setup!(SourceSerif)));
+        assert!(!font.contains(Tag(*b"glyf")));
+        match font.glyph_data() {
+            Err(error) => assert_eq!(error.to_string(), "found no table with tag Tag(loca)"),
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,26 @@
+#[macro_use]
+mod support;
+
+use truetype::tables::FontProgram;
+use truetype::tape::Write;
+use truetype::walue::Read;
+use truetype::Font;
+
+#[test]
+fn read() {
+    let table = ok!(FontProgram::read(&mut setup!(OpenSans, "fpgm"), 1972));
+    assert_eq!(table.len(), 1972);
+    assert_eq!(table[..3], [0x40, 0x47, 0x5B]);
+    assert_eq!(table[1971], 45);
+
+    let mut font = ok!(Font::read(setup!(OpenSans)));
+    assert_eq!(ok!(font.font_program()), &table);
+}
+
+#[test]
+fn write() {
+    let table = ok!(FontProgram::read(&mut setup!(OpenSans, "fpgm"), 1972));
+    let mut data = vec![];
+    ok!(data.give(&table));
+    assert_eq!(data, table.0);
+}
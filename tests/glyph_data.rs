@@ -1,6 +1,15 @@
 #[macro_use]
 mod support;
 
+use std::io::Cursor;
+
+use truetype::tables::glyph_data::{Description, Glyph, SimpleDescription};
+use truetype::tables::{FontHeader, GlyphData, GlyphMapping, MaximumProfile};
+use truetype::value::Read as ValueRead;
+use truetype::walue::Read as WalueRead;
+
+use support::Fixture;
+
 mod open_sans {
     use truetype::value::Read as ValueRead;
     use truetype::walue::Read as WalueRead;
@@ -28,6 +37,14 @@ mod open_sans {
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn write() {
+        use truetype::tables::GlyphMapping;
+
+        let mapping = super::write(super::Fixture::OpenSans);
+        assert!(matches!(mapping, GlyphMapping::HalfOffsets(_)));
+    }
 }
 
 mod ubuntu_condensed {
@@ -103,4 +120,103 @@ mod zen_loop {
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn write() {
+        super::write(super::Fixture::ZenLoop);
+    }
+}
+
+#[test]
+fn write_malformed() {
+    use truetype::tables::glyph_data::CompositeDescription;
+
+    let glyphs = [
+        Glyph {
+            contour_count: 1,
+            ..Default::default()
+        },
+        Glyph {
+            contour_count: 0,
+            description: Description::Composite(CompositeDescription {
+                components: vec![],
+                instruction_size: 0,
+                instructions: vec![],
+            }),
+            ..Default::default()
+        },
+    ];
+    for glyph in glyphs {
+        let table = GlyphData(vec![Some(glyph)]);
+        assert!(table.write(&mut Cursor::new(vec![])).is_err());
+    }
+    let table = GlyphData(vec![Some(Default::default())]);
+    assert!(table.write(&mut Cursor::new(vec![])).is_ok());
+}
+
+fn write(fixture: Fixture) -> GlyphMapping {
+    let setup = |table| support::setup(fixture, Some(table));
+    let parameter1 = ok!(FontHeader::read(&mut setup("head")));
+    let parameter2 = ok!(MaximumProfile::read(&mut setup("maxp")));
+    let parameter = ok!(GlyphMapping::read(
+        &mut setup("loca"),
+        (&parameter1, &parameter2),
+    ));
+    let one = ok!(GlyphData::read(&mut setup("glyf"), &parameter));
+
+    let mut cursor = Cursor::new(vec![]);
+    let parameter = ok!(one.write(&mut cursor));
+    cursor.set_position(0);
+    let other = ok!(GlyphData::read(&mut cursor, &parameter));
+    assert_eq!(one.len(), other.len());
+    for (one, other) in one.iter().zip(other.iter()) {
+        match (one, other) {
+            (Some(one), Some(other)) => compare(one, other),
+            (None, None) => {}
+            _ => unreachable!(),
+        }
+    }
+    parameter
+}
+
+fn compare(one: &Glyph, other: &Glyph) {
+    assert_eq!(one.contour_count, other.contour_count);
+    assert_eq!(
+        (one.min_x, one.min_y, one.max_x, one.max_y),
+        (other.min_x, other.min_y, other.max_x, other.max_y),
+    );
+    match (&one.description, &other.description) {
+        (Description::Simple(one), Description::Simple(other)) => {
+            assert_eq!(one.end_points, other.end_points);
+            assert_eq!(one.instructions, other.instructions);
+            assert_eq!(one.x, other.x);
+            assert_eq!(one.y, other.y);
+            let on_curve = |description: &SimpleDescription| {
+                description
+                    .flags
+                    .iter()
+                    .map(|flag| flag.is_on_curve())
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(on_curve(one), on_curve(other));
+        }
+        (Description::Composite(one), Description::Composite(other)) => {
+            assert_eq!(one.instructions, other.instructions);
+            assert_eq!(one.components.len(), other.components.len());
+            for (one, other) in one.components.iter().zip(other.components.iter()) {
+                assert_eq!(one.glyph_id, other.glyph_id);
+                assert_eq!(one.arguments, other.arguments);
+                assert_eq!(one.options, other.options);
+                assert_eq!(
+                    one.flags.should_round_xy_to_grid(),
+                    other.flags.should_round_xy_to_grid(),
+                );
+                assert_eq!(
+                    one.flags.should_use_metrics(),
+                    other.flags.should_use_metrics(),
+                );
+            }
+        }
+        _ => unreachable!(),
+    }
 }
@@ -15,7 +15,7 @@ fn read() {
         (&parameter1, &parameter2),
     )) {
         GlyphMapping::HalfOffsets(ref offsets) => {
-            assert!(&offsets[0..10] == &[0, 27, 27, 27, 27, 73, 102, 189, 293, 403]);
+            assert!(offsets[0..10] == [0, 27, 27, 27, 27, 73, 102, 189, 293, 403]);
         }
         _ => unreachable!(),
     }
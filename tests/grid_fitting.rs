@@ -0,0 +1,61 @@
+#[macro_use]
+mod support;
+
+use std::io::Cursor;
+
+use truetype::tables::grid_fitting::{Behavior, GridFitting};
+use truetype::tape::Write;
+use truetype::value::Read;
+
+#[test]
+fn write() {
+    let table = GridFitting::new([(8, Behavior(0b0010)), (0xFFFF, Behavior(0b1111))]);
+    assert_eq!(table.version, 1);
+    let mut data = vec![];
+    ok!(data.give(&table));
+    assert_eq!(data, [0, 1, 0, 2, 0, 8, 0, 2, 0xFF, 0xFF, 0, 0xF]);
+    let table = ok!(GridFitting::read(&mut Cursor::new(data)));
+    assert_eq!(table.behavior_at(9), Some(Behavior(0b1111)));
+
+    let table = GridFitting::new([(0xFFFF, Behavior(0b0011))]);
+    assert_eq!(table.version, 0);
+}
+
+mod open_sans {
+    use truetype::tables::grid_fitting::GridFitting;
+    use truetype::value::Read;
+
+    #[test]
+    fn read() {
+        let table = ok!(GridFitting::read(&mut setup!(OpenSans, "gasp")));
+        assert_eq!(table.version, 1);
+        assert_eq!(table.ranges.len(), 3);
+        let behavior = ok!(table.behavior_at(8));
+        assert!(behavior.should_do_gray());
+        assert!(behavior.should_smooth_symmetrically());
+        assert!(!behavior.should_grid_fit());
+        let behavior = ok!(table.behavior_at(12));
+        assert!(behavior.should_grid_fit());
+        assert!(behavior.should_grid_fit_symmetrically());
+        assert!(!behavior.should_smooth_symmetrically());
+        let behavior = ok!(table.behavior_at(100));
+        assert!(behavior.should_grid_fit());
+        assert!(behavior.should_do_gray());
+        assert!(behavior.should_smooth_symmetrically());
+    }
+}
+
+mod vera_mono {
+    use truetype::tables::grid_fitting::GridFitting;
+    use truetype::value::Read;
+
+    #[test]
+    fn read() {
+        let table = ok!(GridFitting::read(&mut setup!(VeraMono, "gasp")));
+        assert_eq!(table.version, 0);
+        assert_eq!(table.ranges.len(), 2);
+        assert!(!ok!(table.behavior_at(8)).should_grid_fit());
+        assert!(ok!(table.behavior_at(9)).should_grid_fit());
+        assert!(ok!(table.behavior_at(9)).should_do_gray());
+    }
+}
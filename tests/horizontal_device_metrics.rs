@@ -0,0 +1,110 @@
+#[macro_use]
+mod support;
+
+use truetype::walue::Read as WalueRead;
+
+#[test]
+fn read() {
+    use truetype::tables::{HorizontalDeviceMetrics, MaximumProfile};
+    use truetype::value::Read;
+
+    let profile = ok!(MaximumProfile::read(&mut setup!(UbuntuCondensed, "maxp")));
+    let table = ok!(HorizontalDeviceMetrics::read(
+        &mut setup!(UbuntuCondensed, "hdmx"),
+        &profile,
+    ));
+    assert_eq!(table.record_count, 22);
+    assert_eq!(table.record_size, 1268);
+    assert_eq!(table.records.len(), 22);
+    assert!(table
+        .records
+        .iter()
+        .all(|record| record.widths.len() == 1263));
+    assert_eq!(
+        (table.records[0].pixel_size, table.records[0].max_width),
+        (11, 15)
+    );
+    assert_eq!(
+        (table.records[21].pixel_size, table.records[21].max_width),
+        (83, 112)
+    );
+    assert_eq!(table.width(10, 0), None);
+}
+
+#[test]
+fn write() {
+    use truetype::tables::HorizontalDeviceMetrics;
+    use truetype::tape::Write;
+    use truetype::Font;
+
+    let mut font = ok!(Font::read(setup!(VeraMono)));
+    let profile = ok!(font.maximum_profile()).clone();
+    let table = ok!(font.horizontal_device_metrics()).clone();
+    let mut data = vec![];
+    ok!(data.give(&table));
+    assert_eq!(data, ok!(font.take_bytes(truetype::Tag(*b"hdmx"))));
+    let other = ok!(HorizontalDeviceMetrics::read(
+        &mut std::io::Cursor::new(data),
+        &profile,
+    ));
+    assert_eq!(other.records.len(), table.records.len());
+}
+
+mod vera_mono {
+    use truetype::hinting::machine::Machine;
+    use truetype::outline::Resolver;
+    use truetype::tables::glyph_data::Description;
+    use truetype::tables::HorizontalDeviceMetrics;
+    use truetype::tape::Write;
+    use truetype::Font;
+
+    #[test]
+    fn regenerate() {
+        let mut font = ok!(Font::read(setup!(VeraMono)));
+        let table = ok!(font.horizontal_device_metrics()).clone();
+        let profile = ok!(font.maximum_profile()).clone();
+        let metrics = ok!(font.horizontal_metrics()).clone();
+        let mut machine = ok!(Machine::from_font(&mut font));
+        let data = ok!(font.glyph_data());
+        let resolver = Resolver::new(data, &profile);
+        let other = table
+            .records
+            .iter()
+            .map(|record| {
+                ok!(machine.set_ppem(record.pixel_size as u16));
+                let widths = (0..profile.glyph_count())
+                    .map(|glyph_id| {
+                        let glyph_id = glyph_id as u16;
+                        let (advance_width, left_side_bearing) = metrics.get(glyph_id);
+                        let (outline, instructions) = match &data[glyph_id as usize] {
+                            Some(glyph) => (
+                                ok!(resolver.resolve(glyph_id)),
+                                match &glyph.description {
+                                    Description::Simple(description) => {
+                                        description.instructions.clone()
+                                    }
+                                    Description::Composite(description) => {
+                                        description.instructions.clone()
+                                    }
+                                },
+                            ),
+                            _ => (Default::default(), vec![]),
+                        };
+                        let hinted = ok!(machine.hint(
+                            &outline,
+                            &instructions,
+                            advance_width,
+                            left_side_bearing,
+                        ));
+                        hinted.advance_width as u8
+                    })
+                    .collect::<Vec<_>>();
+                (record.pixel_size, widths)
+            })
+            .collect::<HorizontalDeviceMetrics>();
+        let (mut data, mut other_data) = (vec![], vec![]);
+        ok!(data.give(&table));
+        ok!(other_data.give(&other));
+        assert_eq!(other_data, data);
+    }
+}
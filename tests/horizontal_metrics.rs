@@ -18,3 +18,82 @@ fn read() {
     assert!(table.left_side_bearings.len() == 547 - 547);
     assert!(table.get(42) == (549, 45));
 }
+
+#[test]
+fn write() {
+    use truetype::tables::{HorizontalHeader, HorizontalMetrics};
+    use truetype::tape::Write;
+    use truetype::Font;
+
+    let mut font = ok!(Font::read(setup!(OpenSans)));
+    let glyph_count = ok!(font.maximum_profile()).glyph_count();
+    let profile = ok!(font.maximum_profile()).clone();
+    let header = *ok!(font.horizontal_header());
+    let metrics = ok!(font.horizontal_metrics()).clone();
+    let bounds = ok!(font.glyph_data())
+        .iter()
+        .map(|glyph| glyph.as_ref().map(|glyph| (glyph.min_x, glyph.max_x)))
+        .collect::<Vec<_>>();
+
+    let table = (0..glyph_count)
+        .map(|glyph_id| metrics.get(glyph_id as _))
+        .collect::<HorizontalMetrics>();
+    assert!(table.records.len() <= metrics.records.len());
+    assert_eq!(
+        table.records.len() + table.left_side_bearings.len(),
+        glyph_count
+    );
+    let mut other = header;
+    other.update(&table, bounds);
+    assert_eq!(other.horizontal_metric_count as usize, table.records.len());
+    assert_eq!(other.max_advance_width, header.max_advance_width);
+    assert_eq!(other.min_left_side_bearing, header.min_left_side_bearing);
+    assert_eq!(other.min_right_side_bearing, header.min_right_side_bearing);
+    assert_eq!(other.max_x_extent, header.max_x_extent);
+
+    let mut data = vec![];
+    ok!(data.give(&other));
+    ok!(data.give(&table));
+    let mut tape = std::io::Cursor::new(data);
+    let other = ok!(HorizontalHeader::read(&mut tape));
+    let table = ok!(HorizontalMetrics::read(&mut tape, (&other, &profile)));
+    for glyph_id in 0..glyph_count {
+        assert_eq!(table.get(glyph_id as _), metrics.get(glyph_id as _));
+    }
+}
+
+#[test]
+fn compact() {
+    use truetype::tables::HorizontalMetrics;
+
+    let table = [(500, 10), (600, 20), (600, 30), (600, 40)]
+        .into_iter()
+        .collect::<HorizontalMetrics>();
+    assert_eq!(table.records.len(), 2);
+    assert_eq!(table.left_side_bearings, [30, 40]);
+    assert_eq!(table.get(3), (600, 40));
+
+    let table = [(500, 10)].into_iter().collect::<HorizontalMetrics>();
+    assert_eq!(table.records.len(), 1);
+    assert!(table.left_side_bearings.is_empty());
+    assert_eq!(table.get(3), (500, 10));
+}
+
+#[test]
+fn empty() {
+    use truetype::tables::{HorizontalHeader, HorizontalMetrics};
+
+    let table = std::iter::empty().collect::<HorizontalMetrics>();
+    assert_eq!(table.records.len(), 1);
+    assert!(table.left_side_bearings.is_empty());
+    assert_eq!(table.get(0), (0, 0));
+    let mut header = HorizontalHeader::default();
+    header.update(&table, []);
+    assert_eq!(header.horizontal_metric_count, 1);
+
+    let table = HorizontalMetrics {
+        records: vec![],
+        left_side_bearings: vec![],
+    };
+    assert_eq!(table.get(1), (0, 0));
+}
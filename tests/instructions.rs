@@ -0,0 +1,45 @@
+#[macro_use]
+mod support;
+
+mod open_sans {
+    use truetype::hinting::instructions::{self, Instruction};
+    use truetype::tables::glyph_data::Description;
+    use truetype::Font;
+
+    #[test]
+    fn round_trip() {
+        let mut font = ok!(Font::read(setup!(OpenSans)));
+        let mut programs = vec![
+            ok!(font.font_program()).0.clone(),
+            ok!(font.control_value_program()).0.clone(),
+        ];
+        for glyph in ok!(font.glyph_data()).iter().flatten() {
+            match &glyph.description {
+                Description::Simple(description) => programs.push(description.instructions.clone()),
+                Description::Composite(description) => {
+                    programs.push(description.instructions.clone())
+                }
+            }
+        }
+        assert_eq!(programs.iter().filter(|data| !data.is_empty()).count(), 767);
+        for data in programs {
+            let decoded = ok!(instructions::decode(&data));
+            assert!(!decoded
+                .iter()
+                .any(|instruction| matches!(instruction, Instruction::Unknown(_))));
+            assert_eq!(ok!(instructions::encode(&decoded)), data);
+            let text = ok!(instructions::disassemble(&data));
+            assert_eq!(ok!(instructions::assemble(&text)), data);
+        }
+    }
+
+    #[test]
+    fn disassemble() {
+        let mut font = ok!(Font::read(setup!(OpenSans)));
+        let text = ok!(instructions::disassemble(ok!(font.font_program())));
+        let lines = text.lines().collect::<Vec<_>>();
+        assert!(lines[0].starts_with("NPUSHB[] 91 90 89 88 85"));
+        assert!(lines.contains(&"FDEF[]"));
+        assert!(lines.iter().any(|line| line.starts_with("  ")));
+    }
+}
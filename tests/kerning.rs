@@ -0,0 +1,80 @@
+#[macro_use]
+mod support;
+
+use std::io::Cursor;
+
+use truetype::tables::kerning::{Format, Kerning};
+use truetype::tape::Write;
+use truetype::value::Read;
+
+#[test]
+fn format2_format3() {
+    let mut data = vec![];
+    ok!(data.give(&[0x00010000u32, 2, 40][..]));
+    ok!(data.give(&[2u16, 0, 4, 16, 24, 32][..]));
+    ok!(data.give(&[10u16, 2, 32, 36][..]));
+    ok!(data.give(&[20u16, 2, 0, 2][..]));
+    ok!(data.give(&[-10i16, -20, -30, -40][..]));
+    ok!(data.give(&28u32));
+    ok!(data.give(&[3u16, 0, 3][..]));
+    ok!(data.give_bytes(&[2, 2, 2, 0]));
+    ok!(data.give(&[0i16, -50][..]));
+    ok!(data.give_bytes(&[0, 1, 0, 0, 0, 1, 0, 0, 0, 1]));
+    let table = ok!(Kerning::read(&mut Cursor::new(data)));
+    match &table {
+        Kerning::Version1(table) => {
+            assert_eq!(table.tables.len(), 2);
+            assert_eq!(table.tables[0].header.coverage.format(), 2);
+            assert!(!table.tables[0].header.coverage.is_vertical());
+            assert!(matches!(table.tables[0].format, Format::Format2(_)));
+            assert!(matches!(table.tables[1].format, Format::Format3(_)));
+        }
+        _ => unreachable!(),
+    }
+    assert_eq!(table.pair(10, 20), -10);
+    assert_eq!(table.pair(11, 21), -40);
+    assert_eq!(table.pair(11, 22), 0);
+    assert_eq!(table.pair(1, 2), -50);
+    assert_eq!(table.pair(0, 2), 0);
+}
+
+mod open_sans {
+    use truetype::tables::kerning::{Format, Kerning};
+    use truetype::value::Read;
+
+    #[test]
+    fn read() {
+        let table = ok!(Kerning::read(&mut setup!(OpenSans, "kern")));
+        match &table {
+            Kerning::Version0(table) => {
+                assert_eq!(table.tables.len(), 1);
+                let coverage = table.tables[0].header.coverage;
+                assert!(coverage.is_horizontal());
+                assert!(!coverage.is_cross_stream());
+                assert!(!coverage.is_override());
+                assert_eq!(coverage.format(), 0);
+                match &table.tables[0].format {
+                    Format::Format0(table) => assert_eq!(table.pairs.len(), 18694),
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+        assert_eq!(table.pair(5, 36), -143);
+        assert_eq!(table.pair(5, 55), 41);
+        assert_eq!(table.pair(5, 5), 0);
+    }
+}
+
+mod ubuntu_condensed {
+    use truetype::tables::kerning::Kerning;
+    use truetype::value::Read;
+
+    #[test]
+    fn read() {
+        let table = ok!(Kerning::read(&mut setup!(UbuntuCondensed, "kern")));
+        assert_eq!(table.pair(6, 437), -10);
+        assert_eq!(table.pair(7, 435), -20);
+        assert_eq!(table.pair(7, 436), 0);
+    }
+}
@@ -0,0 +1,50 @@
+#[macro_use]
+mod support;
+
+use truetype::tables::{LinearThreshold, MaximumProfile};
+use truetype::tape::Write;
+use truetype::value::Read as ValueRead;
+use truetype::walue::Read as WalueRead;
+
+#[test]
+fn read() {
+    let profile = ok!(MaximumProfile::read(&mut setup!(UbuntuCondensed, "maxp")));
+    let table = ok!(LinearThreshold::read(
+        &mut setup!(UbuntuCondensed, "LTSH"),
+        &profile,
+    ));
+    assert_eq!(table.glyph_count, 1263);
+    assert_eq!(table.thresholds.len(), 1263);
+    assert_eq!(table.get(5), Some(42));
+    assert_eq!(table.get(1263), None);
+    assert!(table.is_linear(0, 1));
+    assert!(!table.is_linear(5, 41));
+    assert!(table.is_linear(5, 42));
+}
+
+#[test]
+fn write() {
+    let profile = ok!(MaximumProfile::read(&mut setup!(UbuntuCondensed, "maxp")));
+    let table = ok!(LinearThreshold::read(
+        &mut setup!(UbuntuCondensed, "LTSH"),
+        &profile,
+    ));
+    let other = table
+        .thresholds
+        .iter()
+        .copied()
+        .collect::<LinearThreshold>();
+    let mut data = vec![];
+    ok!(data.give(&other));
+    assert_eq!(data.len(), 1267);
+    let other = ok!(LinearThreshold::read(
+        &mut std::io::Cursor::new(data),
+        &profile,
+    ));
+    assert_eq!(other.thresholds, table.thresholds);
+
+    let other = [1, 2].into_iter().collect::<LinearThreshold>();
+    let mut data = vec![];
+    ok!(data.give(&other));
+    assert!(LinearThreshold::read(&mut std::io::Cursor::new(data), &profile).is_err());
+}
@@ -0,0 +1,128 @@
+#[macro_use]
+mod support;
+
+use truetype::hinting::machine::Machine;
+use truetype::tables::maximum_profile::{MaximumProfile, MaximumProfile1};
+
+#[test]
+fn limits() {
+    let profile = |functions, twilight_points, stack| {
+        MaximumProfile::Version1(MaximumProfile1 {
+            max_function_definitions: functions,
+            max_twilight_points: twilight_points,
+            max_instruction_definitions: 1,
+            max_stack_elements: stack,
+            ..Default::default()
+        })
+    };
+    let machine =
+        |profile, font_program: &[u8]| Machine::new(&profile, 1000, font_program, &[], &[]);
+    // PUSHB[000] 0, FDEF[], PUSHB[000] 0, CALL[], ENDF[], PUSHB[000] 0, CALL[]
+    let recursion = [0xB0, 0, 0x2C, 0xB0, 0, 0x2B, 0x2D, 0xB0, 0, 0x2B];
+    assert!(machine(profile(1, 0, 8), &recursion).is_err());
+    assert!(machine(profile(0, 0, 8), &recursion[..7]).is_err());
+    // PUSHW[000] -3, JMPR[]
+    assert!(machine(profile(0, 0, 8), &[0xB8, 0xFF, 0xFD, 0x1C]).is_err());
+    // NPUSHB[] 34 0 ... 0
+    let push = |count: u8| [&[0x40, count][..], &vec![0; count as usize]].concat();
+    assert!(machine(profile(0, 0, 1), &push(34)).is_err());
+    assert!(machine(profile(0, 0, 1), &push(33)).is_ok());
+    // PUSHW[000] 0x0191, IDEF[], ENDF[]
+    assert!(machine(profile(0, 0, 1), &[0xB8, 0x01, 0x91, 0x89, 0x2D]).is_err());
+    assert!(machine(profile(0, 0, 1), &[0xB8, 0x00, 0x91, 0x89, 0x2D]).is_ok());
+    // PUSHB[001] 0 0, SZP0[], MDAP[1]
+    assert!(machine(profile(0, 0, 2), &[0xB1, 0, 0, 0x13, 0x2F]).is_err());
+    assert!(machine(profile(0, 1, 2), &[0xB1, 0, 0, 0x13, 0x2F]).is_ok());
+}
+
+#[test]
+fn overflow() {
+    let profile = MaximumProfile::Version1(MaximumProfile1 {
+        max_stack_elements: 8,
+        ..Default::default()
+    });
+    let machine = |font_program: &[u8]| Machine::new(&profile, 1000, font_program, &[0], &[]);
+    // PUSHW[001] 0x4000 0x4000, MUL[], PUSHW[000] -0x8000, MUL[], PUSHB[000] 1, SUB[]
+    let maximum = [
+        0xB9, 0x40, 0, 0x40, 0, 0x63, 0xB8, 0x80, 0, 0x63, 0xB0, 1, 0x61,
+    ];
+    let program = |prefix: &[u8], suffix: &[u8]| [prefix, &maximum, suffix].concat();
+    // PUSHB[002] 0 0 1, ..., SDB[], DELTAC2[]
+    assert!(machine(&program(&[0xB2, 0, 0, 1], &[0x5E, 0x74])).is_ok());
+    // PUSHB[000] 0x18, SROUND[], ..., PUSHB[000] 1, ADD[], ROUND[00]
+    assert!(machine(&program(&[0xB0, 0x18, 0x76], &[0xB0, 1, 0x60, 0x68])).is_ok());
+    // ..., PUSHB[000] 1, ADD[], DUP[], SPVFS[]
+    assert!(machine(&program(&[], &[0xB0, 1, 0x60, 0x20, 0x0A])).is_ok());
+}
+
+#[test]
+fn control() {
+    let profile = MaximumProfile::Version1(Default::default());
+    let machine = |font_program: &[u8], control_value_program: &[u8]| {
+        let mut machine = ok!(Machine::new(
+            &profile,
+            1000,
+            font_program,
+            &[],
+            control_value_program,
+        ));
+        ok!(machine.set_ppem(12));
+        machine
+    };
+    // PUSHB[001] 1 1, INSTCTRL[]
+    let control = [0xB1, 1, 1, 0x8E];
+    // POP[]
+    let failing = [0x21];
+    let mut machine1 = machine(&[], &control);
+    assert!(machine1.hint(&Default::default(), &failing, 0, 0).is_ok());
+    let mut machine2 = machine(&control, &[]);
+    assert!(machine2.hint(&Default::default(), &failing, 0, 0).is_err());
+}
+
+mod open_sans {
+    use truetype::hinting::machine::Machine;
+    use truetype::outline::Resolver;
+    use truetype::tables::glyph_data::Description;
+    use truetype::Font;
+
+    #[test]
+    fn hint() {
+        let mut font = ok!(Font::read(setup!(OpenSans)));
+        let mut machine = ok!(Machine::from_font(&mut font));
+        assert!(machine.hint(&Default::default(), &[], 0, 0).is_err());
+        let units_per_em = ok!(font.font_header()).units_per_em as f32;
+        let profile = ok!(font.maximum_profile()).clone();
+        let metrics = ok!(font.horizontal_metrics()).clone();
+        let data = ok!(font.glyph_data());
+        let resolver = Resolver::new(data, &profile);
+        for ppem in [9, 12, 16, 24, 48] {
+            ok!(machine.set_ppem(ppem));
+            assert_eq!(machine.ppem(), ppem);
+            let scale = ppem as f32 / units_per_em;
+            for (glyph_id, glyph) in data.iter().enumerate() {
+                let glyph_id = glyph_id as u16;
+                // The instructions of components are not executed, so composite
+                // glyphs are hinted without instructions, and only their
+                // outlines and advances are checked.
+                let instructions = match glyph.as_ref().map(|glyph| &glyph.description) {
+                    Some(Description::Simple(description)) => &description.instructions[..],
+                    Some(Description::Composite(_)) => &[],
+                    _ => continue,
+                };
+                let outline = ok!(resolver.resolve(glyph_id));
+                let (advance_width, left_side_bearing) = metrics.get(glyph_id);
+                let hinted =
+                    ok!(machine.hint(&outline, instructions, advance_width, left_side_bearing,));
+                assert_eq!(hinted.outline.point_count(), outline.point_count());
+                assert_eq!(hinted.advance_width.fract(), 0.0);
+                assert!((hinted.advance_width - advance_width as f32 * scale).abs() <= 1.0);
+                if glyph_id == 43 {
+                    let points = hinted.outline.iter().flat_map(|contour| contour.iter());
+                    assert!(points.clone().all(|point| point.y.fract() == 0.0));
+                    let top = points.map(|point| point.y).fold(0.0, f32::max);
+                    assert!((top - 1462.0 * scale).abs() <= 1.0);
+                }
+            }
+        }
+    }
+}
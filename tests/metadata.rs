@@ -0,0 +1,124 @@
+#[macro_use]
+mod support;
+
+use std::io::Cursor;
+
+use truetype::tables::metadata::Value;
+use truetype::tables::Metadata;
+use truetype::tape::Write;
+use truetype::value::Read;
+use truetype::Tag;
+
+#[test]
+fn read() {
+    let mut data = vec![];
+    data.extend_from_slice(&[0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3]);
+    data.extend_from_slice(b"dlng\0\0\0\x34\0\0\0\x0D");
+    data.extend_from_slice(b"slng\0\0\0\x41\0\0\0\x16");
+    data.extend_from_slice(b"APPL\0\0\0\x57\0\0\0\x03");
+    data.extend_from_slice(b"Latn,  Cyrl ,");
+    data.extend_from_slice(b"Latn, Cyrl, Grek, Jpan");
+    data.extend_from_slice(&[1, 2, 3]);
+
+    let table = ok!(Metadata::read(&mut Cursor::new(&data)));
+    assert_eq!(table.record_count, 3);
+    assert_eq!(
+        table.design_languages(),
+        Some(&["Latn", "Cyrl"].map(String::from)[..])
+    );
+    assert_eq!(
+        table.supported_languages(),
+        Some(&["Latn", "Cyrl", "Grek", "Jpan"].map(String::from)[..]),
+    );
+    assert_eq!(table.get(Tag(*b"APPL")), Some(&Value::Data(vec![1, 2, 3])));
+    assert_eq!(table.get(Tag(*b"none")), None);
+}
+
+#[test]
+fn read_malformed() {
+    let mut data = vec![0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
+    data.extend_from_slice(b"slng\0\0\0\x1C\0\0\0\x02");
+    data.extend_from_slice(&[0xFF, 0xFE]);
+    assert!(Metadata::read(&mut Cursor::new(&data)).is_err());
+}
+
+#[test]
+fn write() {
+    let table = [
+        (
+            Tag(*b"dlng"),
+            Value::Languages(vec!["Latn".into(), "Cyrl".into()]),
+        ),
+        (Tag(*b"slng"), Value::Languages(vec!["Latn".into()])),
+        (Tag(*b"APPL"), Value::Data(vec![1, 2, 3])),
+    ]
+    .into_iter()
+    .collect::<Metadata>();
+    assert_eq!(table.record_count, 3);
+    assert_eq!(
+        table
+            .records
+            .iter()
+            .map(|record| (record.offset, record.size))
+            .collect::<Vec<_>>(),
+        [(52, 10), (62, 4), (66, 3)],
+    );
+
+    let mut data = vec![];
+    ok!(data.give(&table));
+    assert_eq!(data.len(), 69);
+    assert_eq!(&data[52..66], b"Latn, CyrlLatn");
+
+    let other = ok!(Metadata::read(&mut Cursor::new(&data)));
+    assert_eq!(
+        other
+            .records
+            .iter()
+            .map(|record| (record.tag, record.offset, record.size))
+            .collect::<Vec<_>>(),
+        table
+            .records
+            .iter()
+            .map(|record| (record.tag, record.offset, record.size))
+            .collect::<Vec<_>>(),
+    );
+    assert_eq!(other.values, table.values);
+}
+
+#[test]
+fn write_malformed() {
+    for value in ["Latn,Cyrl", " Latn", "Latn ", ""] {
+        let table = [(Tag(*b"slng"), Value::Languages(vec![value.into()]))]
+            .into_iter()
+            .collect::<Metadata>();
+        assert_eq!((table.records[0].offset, table.records[0].size), (0, 0));
+        let mut data = vec![];
+        assert!(data.give(&table).is_err());
+    }
+}
+
+#[test]
+fn font() {
+    use truetype::{Font, FontBuilder};
+
+    let table = [(
+        Tag(*b"dlng"),
+        Value::Languages(vec!["Latn".into(), "Grek".into(), "Cyrl".into()]),
+    )]
+    .into_iter()
+    .collect::<Metadata>();
+    let mut font = ok!(Font::read(setup!(OpenSans)));
+    let mut builder = ok!(FontBuilder::new(Tag([0, 1, 0, 0])));
+    for tag in [*b"head", *b"maxp"] {
+        builder.add(Tag(tag), ok!(font.take_bytes(Tag(tag))));
+    }
+    ok!(builder.add_table(Tag(*b"meta"), &table));
+    let mut font = ok!(Font::read(Cursor::new(ok!(builder.build()))));
+    let data = ok!(font.take_bytes(Tag(*b"meta")));
+    let other = ok!(Metadata::read(&mut Cursor::new(&data)));
+    assert_eq!(
+        other.design_languages(),
+        Some(&["Latn", "Grek", "Cyrl"].map(String::from)[..]),
+    );
+    assert_eq!(other.supported_languages(), None);
+}
@@ -199,7 +199,7 @@ mod source_serif {
                 value.is_some()
                     && language_id
                         .tag(&language_tags)
-                        .map_or(false, |tag| tag.starts_with("en"))
+                        .is_some_and(|tag| tag.starts_with("en"))
             })
             .map(|((_, _, _, name_id), value)| (name_id, ok!(value)))
             .collect::<HashMap<_, _>>();
@@ -0,0 +1,154 @@
+#[macro_use]
+mod support;
+
+mod open_sans {
+    use truetype::outline::{Outline, Pen, Point, Resolver};
+    use truetype::tables::glyph_data::Description;
+    use truetype::Font;
+
+    #[derive(Default)]
+    struct Recorder(Vec<String>);
+
+    impl Pen for Recorder {
+        fn move_to(&mut self, x: f32, y: f32) {
+            self.0.push(format!("M {x} {y}"));
+        }
+
+        fn line_to(&mut self, x: f32, y: f32) {
+            self.0.push(format!("L {x} {y}"));
+        }
+
+        fn quad_to(&mut self, control_x: f32, control_y: f32, x: f32, y: f32) {
+            self.0.push(format!("Q {control_x} {control_y} {x} {y}"));
+        }
+
+        fn close(&mut self) {
+            self.0.push("Z".into());
+        }
+    }
+
+    #[test]
+    fn draw() {
+        let mut font = ok!(Font::read(setup!(OpenSans)));
+        let table = ok!(font.glyph_data());
+        let outline = match ok!(table[0].as_ref()).description {
+            Description::Simple(ref description) => Outline::from(description),
+            _ => unreachable!(),
+        };
+        let mut pen = Recorder::default();
+        outline.draw(&mut pen);
+        assert_eq!(
+            pen.0,
+            [
+                "M 193 1462",
+                "L 1034 1462",
+                "L 1034 0",
+                "L 193 0",
+                "L 193 1462",
+                "Z",
+                "M 297 104",
+                "L 930 104",
+                "L 930 1358",
+                "L 297 1358",
+                "L 297 104",
+                "Z",
+            ],
+        );
+    }
+
+    #[test]
+    fn resolve() {
+        let mut font = ok!(Font::read(setup!(OpenSans)));
+        let profile = ok!(font.maximum_profile()).clone();
+        let table = ok!(font.glyph_data());
+        let resolver = Resolver::new(table, &profile);
+        let count = (0..table.len())
+            .map(|glyph_id| ok!(resolver.resolve(glyph_id as _)).point_count())
+            .sum::<usize>();
+        assert_eq!(count, 29764);
+    }
+
+    #[test]
+    fn from() {
+        let mut font = ok!(Font::read(setup!(OpenSans)));
+        let table = ok!(font.glyph_data());
+        for glyph in table.iter().flatten() {
+            if let Description::Simple(ref description) = glyph.description {
+                let outline = Outline::from(description);
+                assert_eq!(outline.len(), glyph.contour_count as usize);
+                if let Some((min_x, min_y, max_x, max_y)) = outline.bounds() {
+                    assert_eq!(min_x, glyph.min_x as f32);
+                    assert_eq!(min_y, glyph.min_y as f32);
+                    assert_eq!(max_x, glyph.max_x as f32);
+                    assert_eq!(max_y, glyph.max_y as f32);
+                }
+            }
+        }
+        let outline = match ok!(table[0].as_ref()).description {
+            Description::Simple(ref description) => Outline::from(description),
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            outline[0][..2],
+            [
+                Point {
+                    x: 193.0,
+                    y: 1462.0,
+                    on_curve: true
+                },
+                Point {
+                    x: 1034.0,
+                    y: 1462.0,
+                    on_curve: true
+                },
+            ],
+        );
+    }
+}
+
+mod zen_loop {
+    use truetype::outline::Resolver;
+    use truetype::tables::glyph_data::Description;
+    use truetype::Font;
+
+    #[test]
+    fn resolve() {
+        let mut font = ok!(Font::read(setup!(ZenLoop)));
+        let profile = ok!(font.maximum_profile()).clone();
+        let table = ok!(font.glyph_data());
+        let resolver = Resolver::new(table, &profile);
+        let one = ok!(resolver.resolve(70));
+        let mut other = ok!(resolver.resolve(72));
+        other.transform(|point| point.x = 298.0 - point.x);
+        assert_eq!(one, other);
+        for (glyph_id, glyph) in table.iter().enumerate() {
+            let glyph = match glyph {
+                Some(glyph) => glyph,
+                _ => continue,
+            };
+            if let Description::Composite(_) = glyph.description {
+                let outline = ok!(resolver.resolve(glyph_id as _));
+                let (min_x, min_y, max_x, max_y) = ok!(outline.bounds());
+                assert!((min_x - glyph.min_x as f32).abs() < 1.0);
+                assert!((min_y - glyph.min_y as f32).abs() < 1.0);
+                assert!((max_x - glyph.max_x as f32).abs() < 1.0);
+                assert!((max_y - glyph.max_y as f32).abs() < 1.0);
+            }
+        }
+    }
+
+    #[test]
+    fn resolve_without_depth() {
+        use truetype::tables::maximum_profile::{MaximumProfile, MaximumProfile0};
+
+        let mut font = ok!(Font::read(setup!(ZenLoop)));
+        let mut profile = ok!(font.maximum_profile()).clone();
+        let table = ok!(font.glyph_data());
+        if let MaximumProfile::Version1(ref mut profile) = profile {
+            profile.max_component_depth = 0;
+        }
+        ok!(Resolver::new(table, &profile).resolve(72));
+        let profile = MaximumProfile::Version0(MaximumProfile0::default());
+        ok!(Resolver::new(table, &profile).resolve(72));
+    }
+}
@@ -0,0 +1,59 @@
+#[macro_use]
+mod support;
+
+use truetype::tables::pcl::{SerifClass, SerifStyle, StrokeWeight};
+use truetype::tables::PrinterCommandLanguage;
+use truetype::value::Read;
+
+#[test]
+fn read() {
+    let table = ok!(PrinterCommandLanguage::read(&mut setup!(VeraMono, "PCLT")));
+    assert_eq!(table.font_number, 0x42001DB9);
+    assert_eq!(
+        (table.pitch, table.x_height, table.cap_height),
+        (1233, 1120, 1493)
+    );
+    assert_eq!(table.type_family, 0x2363);
+    assert_eq!(table.typeface, b"VeraSansMono\0\0\0\0");
+    assert_eq!(
+        table.character_complement,
+        [0xFF, 0xFF, 0xFF, 0xFF, 0x36, 0xFF, 0xFF, 0xFE]
+    );
+    assert_eq!(table.file_name, b"628R00");
+    assert_eq!(table.stroke_weight, StrokeWeight::Book);
+    assert_eq!(table.width_type, 0);
+    assert_eq!(table.serif_style(), SerifStyle::SansSerifSquare);
+    assert_eq!(table.serif_class(), SerifClass::SansSerif);
+}
+
+#[test]
+fn write() {
+    use std::io::{Cursor, Read, Seek, SeekFrom};
+    use truetype::tape::Write;
+
+    let mut table = ok!(PrinterCommandLanguage::read(&mut setup!(VeraMono, "PCLT")));
+    let mut data = vec![];
+    ok!(data.give(&table));
+    let mut file = setup!(VeraMono);
+    let mut other = vec![0; 54];
+    ok!(file.seek(SeekFrom::Start(43652)));
+    ok!(file.read_exact(&mut other));
+    assert_eq!(data, other);
+
+    table.stroke_weight = StrokeWeight::Unknown(-8);
+    table.serif_bits = 0b1000_0110;
+    let mut data = vec![];
+    ok!(data.give(&table));
+    let mut table = ok!(PrinterCommandLanguage::read(&mut Cursor::new(data)));
+    assert_eq!(table.stroke_weight, StrokeWeight::Unknown(-8));
+    assert_eq!(i8::from(table.stroke_weight), -8);
+    assert_eq!(table.serif_style(), SerifStyle::SerifBracket);
+    assert_eq!(table.serif_class(), SerifClass::Serif);
+
+    let mut data = vec![];
+    table.file_name.pop();
+    assert!(data.give(&table).is_err());
+    table.file_name.push(b'0');
+    table.typeface.push(0);
+    assert!(data.give(&table).is_err());
+}
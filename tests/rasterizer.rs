@@ -0,0 +1,111 @@
+#[macro_use]
+mod support;
+
+use truetype::outline::{Contour, Outline, Point};
+use truetype::rasterizer::Bitmap;
+
+fn square(x: f32, y: f32, size: f32, clockwise: bool) -> Contour {
+    let mut points = vec![(x, y), (x, y + size), (x + size, y + size), (x + size, y)];
+    if !clockwise {
+        points.reverse();
+    }
+    Contour(
+        points
+            .into_iter()
+            .map(|(x, y)| Point {
+                x,
+                y,
+                on_curve: true,
+            })
+            .collect(),
+    )
+}
+
+#[test]
+fn render() {
+    let outline = Outline(vec![square(0.0, 0.0, 1000.0, true)]);
+    let bitmap = ok!(Bitmap::render(&outline, 1000, 10.0, (0.0, 0.0)));
+    assert_eq!((bitmap.width, bitmap.height), (10, 10));
+    assert_eq!((bitmap.left, bitmap.top), (0, 10));
+    assert!(bitmap.data.iter().all(|&value| value == 255));
+
+    let bitmap = ok!(Bitmap::render(&outline, 1000, 10.0, (0.5, -0.25)));
+    assert_eq!((bitmap.width, bitmap.height), (11, 11));
+    assert_eq!((bitmap.left, bitmap.top), (0, 10));
+    assert_eq!(bitmap.get(0, 5), 128);
+    assert_eq!(bitmap.get(5, 5), 255);
+    assert_eq!(bitmap.get(10, 5), 128);
+    assert_eq!(bitmap.get(5, 0), 191);
+    assert_eq!(bitmap.get(5, 10), 64);
+    assert_eq!(bitmap.get(0, 0), 96);
+}
+
+#[test]
+fn winding() {
+    let outline = Outline(vec![
+        square(0.0, 0.0, 1000.0, true),
+        square(0.0, 0.0, 1000.0, true),
+        square(250.0, 250.0, 500.0, false),
+    ]);
+    let bitmap = ok!(Bitmap::render(&outline, 1000, 8.0, (0.0, 0.0)));
+    assert!(bitmap.data.iter().all(|&value| value == 255));
+
+    let outline = Outline(vec![
+        square(0.0, 0.0, 1000.0, true),
+        square(250.0, 250.0, 500.0, false),
+    ]);
+    let bitmap = ok!(Bitmap::render(&outline, 1000, 8.0, (0.0, 0.0)));
+    assert_eq!(bitmap.get(0, 0), 255);
+    assert_eq!(bitmap.get(3, 3), 0);
+    assert_eq!(bitmap.get(4, 4), 0);
+    assert_eq!(bitmap.get(7, 7), 255);
+}
+
+#[test]
+fn to_pgm() {
+    let bitmap = Bitmap {
+        width: 2,
+        height: 1,
+        left: 0,
+        top: 0,
+        data: vec![0, 255],
+    };
+    assert_eq!(bitmap.to_pgm(), b"P5\n2 1\n255\n\x00\xFF");
+}
+
+mod open_sans {
+    use truetype::outline::Resolver;
+    use truetype::rasterizer::Bitmap;
+    use truetype::Font;
+
+    #[test]
+    fn render() {
+        let mut font = ok!(Font::read(setup!(OpenSans)));
+        let units_per_em = ok!(font.font_header()).units_per_em;
+        let profile = ok!(font.maximum_profile()).clone();
+        let resolver = Resolver::new(ok!(font.glyph_data()), &profile);
+        let outline = ok!(resolver.resolve(43));
+        let scale = 64.0 / units_per_em as f32;
+        let area = outline
+            .iter()
+            .map(|contour| {
+                let count = contour.len();
+                (0..count)
+                    .map(|i| {
+                        let (a, b) = (contour[i], contour[(i + 1) % count]);
+                        a.x * b.y - b.x * a.y
+                    })
+                    .sum::<f32>()
+                    / 2.0
+            })
+            .sum::<f32>()
+            .abs()
+            * scale
+            * scale;
+        let bitmap = ok!(Bitmap::render(&outline, units_per_em, 64.0, (0.0, 0.0)));
+        assert_eq!((bitmap.left, bitmap.top), (2, 46));
+        let coverage = bitmap.data.iter().map(|&value| value as f32).sum::<f32>() / 255.0;
+        assert!((coverage - area).abs() / area < 0.01);
+        assert_eq!(bitmap.get(bitmap.width / 2, bitmap.height - 1), 0);
+    }
+}
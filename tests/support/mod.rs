@@ -55,7 +55,10 @@ impl Fixture {
     pub fn offset(&self, table: &str) -> u64 {
         match *self {
             Fixture::AoboshiOne => match table {
+                "maxp" => 105880,
                 "name" => 105912,
+                "vhea" => 112084,
+                "vmtx" => 112120,
                 _ => unreachable!(),
             },
             Fixture::BungeeColor => match table {
@@ -72,16 +75,25 @@ impl Fixture {
             },
             Fixture::MPlus2P => match table {
                 "cmap" => 36100,
+                "maxp" => 440,
+                "vhea" => 1745712,
+                "vmtx" => 1721676,
                 _ => unreachable!(),
             },
             Fixture::OpenSans => match table {
+                "DSIG" => 207404,
                 "cmap" => 4276,
+                "cvt " => 7568,
+                "fpgm" => 5328,
+                "gasp" => 206332,
                 "glyf" => 9608,
                 "head" => 316,
+                "kern" => 82856,
                 "loca" => 7728,
                 "maxp" => 408,
                 "name" => 195040,
                 "post" => 196560,
+                "prep" => 7300,
                 _ => unreachable!(),
             },
             Fixture::SourceSerif => match table {
@@ -96,17 +108,26 @@ impl Fixture {
                 _ => unreachable!(),
             },
             Fixture::UbuntuCondensed => match table {
+                "LTSH" => 5620,
+                "VDMX" => 6888,
                 "glyf" => 42520,
+                "hdmx" => 8392,
                 "head" => 348,
+                "kern" => 172840,
                 "loca" => 39992,
                 "maxp" => 440,
                 _ => unreachable!(),
             },
             Fixture::VeraMono => match table {
+                "PCLT" => 43652,
                 "cmap" => 40360,
+                "gasp" => 49156,
+                "hdmx" => 43708,
+                "maxp" => 43496,
                 _ => unreachable!(),
             },
             Fixture::ZenLoop => match table {
+                "DSIG" => 43056,
                 "glyf" => 6404,
                 "head" => 300,
                 "loca" => 5900,
@@ -0,0 +1,69 @@
+#[macro_use]
+mod support;
+
+use truetype::tables::VerticalDeviceMetrics;
+use truetype::value::Read;
+
+#[test]
+fn read() {
+    let table = ok!(VerticalDeviceMetrics::read(&mut setup!(
+        UbuntuCondensed,
+        "VDMX"
+    )));
+    assert_eq!(table.version, 1);
+    assert_eq!((table.group_count, table.range_count), (1, 1));
+    assert_eq!(table.indices, [0]);
+    assert_eq!(table.groups.len(), 1);
+    let group = ok!(table.group(0));
+    assert_eq!(
+        (group.record_count, group.start_size, group.end_size),
+        (248, 8, 255)
+    );
+    assert_eq!(table.bounds(8, 1, 1), Some((52, -3)));
+    assert_eq!(table.bounds(10, 1, 1), Some((12, -3)));
+    assert_eq!(table.bounds(10, 2, 1), None);
+    assert_eq!(table.bounds(7, 1, 1), None);
+    assert!(table.group(1).is_none());
+}
+
+#[test]
+fn shared() {
+    use std::io::Cursor;
+    use truetype::tape::Write;
+
+    #[rustfmt::skip]
+    let data = vec![
+        0, 1, 0, 2, 0, 3,
+        1, 2, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0,
+        0, 34, 0, 24, 0, 34,
+        0, 1, 8, 8, 0, 8, 0, 10, 0xFF, 0xFE,
+        0, 1, 9, 9, 0, 9, 0, 11, 0xFF, 0xFD,
+    ];
+    let table = ok!(VerticalDeviceMetrics::read(&mut Cursor::new(data.clone())));
+    assert_eq!(table.indices, [1, 0, 1]);
+    assert_eq!(table.bounds(9, 2, 1), Some((11, -3)));
+    assert_eq!(table.bounds(8, 1, 1), Some((10, -2)));
+    assert_eq!(table.bounds(9, 3, 1), Some((11, -3)));
+    let mut other = vec![];
+    ok!(other.give(&table));
+    assert_eq!(other, data);
+}
+
+#[test]
+fn write() {
+    use std::io::{Read, Seek, SeekFrom};
+    use truetype::tape::Write;
+
+    let table = ok!(VerticalDeviceMetrics::read(&mut setup!(
+        UbuntuCondensed,
+        "VDMX"
+    )));
+    let mut data = vec![];
+    ok!(data.give(&table));
+    assert_eq!(data.len(), 1504);
+    let mut file = setup!(UbuntuCondensed);
+    let mut other = vec![0; 1504];
+    ok!(file.seek(SeekFrom::Start(6888)));
+    ok!(file.read_exact(&mut other));
+    assert_eq!(data, other);
+}
@@ -0,0 +1,16 @@
+#[macro_use]
+mod support;
+
+use truetype::value::Read;
+
+#[test]
+fn read() {
+    use truetype::tables::VerticalHeader;
+
+    let table = ok!(VerticalHeader::read(&mut setup!(MPlus2P, "vhea")));
+    assert!(table.minor_version == 0x1000);
+    assert!(table.ascender == 908);
+    assert!(table.descender == -615);
+    assert!(table.max_advance_height == 1000);
+    assert!(table.vertical_metric_count == 2766);
+}
@@ -0,0 +1,108 @@
+#[macro_use]
+mod support;
+
+use truetype::value::Read as ValueRead;
+use truetype::walue::Read as WalueRead;
+
+#[test]
+fn read() {
+    use truetype::tables::{MaximumProfile, VerticalHeader, VerticalMetrics};
+
+    let parameter1 = ok!(VerticalHeader::read(&mut setup!(MPlus2P, "vhea")));
+    let parameter2 = ok!(MaximumProfile::read(&mut setup!(MPlus2P, "maxp")));
+    let table = ok!(VerticalMetrics::read(
+        &mut setup!(MPlus2P, "vmtx"),
+        (&parameter1, &parameter2),
+    ));
+    assert!(table.records.len() == 2766);
+    assert!(table.top_side_bearings.len() == 8892 - 2766);
+    assert!(table.get(0) == (1000, 666));
+    assert!(table.get(42) == (1000, 120));
+    assert!(table.get(2766) == (1000, 632));
+}
+
+#[test]
+fn update() {
+    use truetype::tables::{VerticalHeader, VerticalMetrics};
+    use truetype::Font;
+
+    // The top side bearing is measured from the top of the advance down to
+    // the maximum y coordinate.
+    let table = [(1000, 120), (1000, 200), (1000, 50)]
+        .into_iter()
+        .collect::<VerticalMetrics>();
+    let mut header = VerticalHeader::default();
+    header.update(&table, [Some((-100, 780)), None, Some((0, 1000))]);
+    assert_eq!(header.vertical_metric_count, 1);
+    assert_eq!(header.max_advance_height, 1000);
+    assert_eq!(header.min_top_side_bearing, 50);
+    assert_eq!(header.min_bottom_side_bearing, -50);
+    assert_eq!(header.max_y_extent, 1050);
+
+    let mut font = ok!(Font::read(setup!(AoboshiOne)));
+    let header = *ok!(font.vertical_header());
+    let metrics = ok!(font.vertical_metrics()).clone();
+    let bounds = ok!(font.glyph_data())
+        .iter()
+        .map(|glyph| glyph.as_ref().map(|glyph| (glyph.min_y, glyph.max_y)))
+        .collect::<Vec<_>>();
+    let mut other = header;
+    other.update(&metrics, bounds);
+    assert_eq!(other.min_top_side_bearing, header.min_top_side_bearing);
+    assert_eq!(
+        other.min_bottom_side_bearing,
+        header.min_bottom_side_bearing
+    );
+    assert_eq!(other.max_y_extent, header.max_y_extent);
+}
+
+#[test]
+fn empty() {
+    use truetype::tables::{VerticalHeader, VerticalMetrics};
+
+    let table = std::iter::empty().collect::<VerticalMetrics>();
+    assert_eq!(table.records.len(), 1);
+    assert!(table.top_side_bearings.is_empty());
+    assert_eq!(table.get(0), (0, 0));
+    let mut header = VerticalHeader::default();
+    header.update(&table, []);
+    assert_eq!(header.vertical_metric_count, 1);
+
+    let table = VerticalMetrics {
+        records: vec![],
+        top_side_bearings: vec![],
+    };
+    assert_eq!(table.get(1), (0, 0));
+}
+
+#[test]
+fn write() {
+    use truetype::tables::{MaximumProfile, VerticalHeader, VerticalMetrics};
+    use truetype::tape::Write;
+
+    let header = ok!(VerticalHeader::read(&mut setup!(MPlus2P, "vhea")));
+    let profile = ok!(MaximumProfile::read(&mut setup!(MPlus2P, "maxp")));
+    let metrics = ok!(VerticalMetrics::read(
+        &mut setup!(MPlus2P, "vmtx"),
+        (&header, &profile),
+    ));
+    let glyph_count = profile.glyph_count();
+
+    let table = (0..glyph_count)
+        .map(|glyph_id| metrics.get(glyph_id as _))
+        .collect::<VerticalMetrics>();
+    assert_eq!(table.records.len(), 2766);
+    assert_eq!(table.top_side_bearings.len(), glyph_count - 2766);
+
+    let mut other = header;
+    other.vertical_metric_count = table.records.len() as _;
+    let mut data = vec![];
+    ok!(data.give(&table));
+    let table = ok!(VerticalMetrics::read(
+        &mut std::io::Cursor::new(data),
+        (&other, &profile),
+    ));
+    for glyph_id in 0..glyph_count {
+        assert_eq!(table.get(glyph_id as _), metrics.get(glyph_id as _));
+    }
+}